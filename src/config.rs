@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::ExporterConfig;
+
+/// 项目配置文件名，在当前目录中自动查找
+pub const CONFIG_FILE_NAME: &str = "excel-cli.toml";
+
+/// 一组默认选项：全局默认值或一个命名配置
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigProfile {
+    /// 输出格式
+    pub format: Option<String>,
+    /// 工作表名称
+    pub sheet: Option<String>,
+    /// 选择的列
+    pub select: Option<Vec<String>>,
+    /// 排除的列
+    pub exclude: Option<Vec<String>>,
+    /// 过滤条件
+    pub filter: Option<Vec<String>>,
+    /// 导出器选项，名称与 `ExporterConfig` 字段一致
+    pub options: ExporterConfig,
+}
+
+impl ConfigProfile {
+    /// 用 `defaults` 补全未设置的选项
+    pub fn with_defaults(self, defaults: ConfigProfile) -> Self {
+        Self {
+            format: self.format.or(defaults.format),
+            sheet: self.sheet.or(defaults.sheet),
+            select: self.select.or(defaults.select),
+            exclude: self.exclude.or(defaults.exclude),
+            filter: self.filter.or(defaults.filter),
+            options: self.options.with_defaults(defaults.options),
+        }
+    }
+}
+
+/// 项目配置文件 `excel-cli.toml`：顶层为全局默认值，`[profiles.名称]` 为命名配置
+#[derive(Debug, Default, Clone)]
+pub struct ProjectConfig {
+    /// 全局默认值
+    pub defaults: ConfigProfile,
+    /// 命名配置，通过 `--profile` 选择
+    pub profiles: BTreeMap<String, ConfigProfile>,
+}
+
+/// 配置文件的原始结构，全局默认值与 `profiles` 位于同一层
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawProjectConfig {
+    format: Option<String>,
+    sheet: Option<String>,
+    select: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    filter: Option<Vec<String>>,
+    options: ExporterConfig,
+    profiles: BTreeMap<String, ConfigProfile>,
+}
+
+impl ProjectConfig {
+    /// 解析配置文件内容，未知的选项会报错
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        let raw: RawProjectConfig = toml::from_str(s.trim_start_matches('\u{FEFF}'))
+            .map_err(|e| ExcelCliError::ExportError(format!("配置文件解析失败: {}", e)))?;
+        Ok(Self {
+            defaults: ConfigProfile {
+                format: raw.format,
+                sheet: raw.sheet,
+                select: raw.select,
+                exclude: raw.exclude,
+                filter: raw.filter,
+                options: raw.options,
+            },
+            profiles: raw.profiles,
+        })
+    }
+
+    /// 读取配置文件：指定路径时必须存在，否则在当前目录查找 `excel-cli.toml`，不存在时返回 None
+    pub fn discover(path: Option<&str>) -> Result<Option<Self>> {
+        let path = match path {
+            Some(path) => {
+                if !Path::new(path).exists() {
+                    return Err(ExcelCliError::ExportError(format!(
+                        "配置文件不存在: {}",
+                        path
+                    )));
+                }
+                path
+            }
+            None if Path::new(CONFIG_FILE_NAME).exists() => CONFIG_FILE_NAME,
+            None => return Ok(None),
+        };
+        Self::from_str(&std::fs::read_to_string(path)?).map(Some)
+    }
+
+    /// 合并全局默认值和命名配置，命名配置中的选项优先
+    pub fn resolve(&self, profile: Option<&str>) -> Result<ConfigProfile> {
+        let Some(name) = profile else {
+            return Ok(self.defaults.clone());
+        };
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            ExcelCliError::ExportError(format!(
+                "配置 '{}' 不存在，可用的配置: {}",
+                name,
+                self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        Ok(profile.clone().with_defaults(self.defaults.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_profiles() {
+        let config = ProjectConfig::from_str(
+            r#"
+sheet = "data"
+filter = ["Status != deleted"]
+
+[options]
+sql_dialect = "postgresql"
+sql_table = "orders"
+primary_keys = ["ID"]
+
+[profiles.warehouse]
+format = "sql"
+
+[profiles.warehouse.options]
+sql_dialect = "snowflake"
+sql_transaction = true
+"#,
+        )
+        .unwrap();
+
+        let defaults = config.resolve(None).unwrap();
+        assert_eq!(defaults.format, None);
+        assert_eq!(defaults.options.sql_dialect.as_deref(), Some("postgresql"));
+
+        let warehouse = config.resolve(Some("warehouse")).unwrap();
+        assert_eq!(warehouse.format.as_deref(), Some("sql"));
+        assert_eq!(warehouse.sheet.as_deref(), Some("data"));
+        assert_eq!(warehouse.options.sql_dialect.as_deref(), Some("snowflake"));
+        assert_eq!(warehouse.options.sql_table.as_deref(), Some("orders"));
+        assert_eq!(warehouse.options.sql_transaction, Some(true));
+
+        // 命令行参数优先于配置文件
+        let cli = ExporterConfig {
+            sql_table: Some("orders_v2".to_string()),
+            ..Default::default()
+        };
+        let merged = cli.with_defaults(warehouse.options);
+        assert_eq!(merged.sql_table.as_deref(), Some("orders_v2"));
+        assert_eq!(merged.primary_keys, Some(vec!["ID".to_string()]));
+        assert_eq!(merged.sql_transaction, Some(true));
+
+        // 命令行可以关闭配置文件中开启的开关
+        let cli = ExporterConfig {
+            sql_transaction: Some(false),
+            ..Default::default()
+        };
+        let merged = cli.with_defaults(config.resolve(Some("warehouse")).unwrap().options);
+        assert_eq!(merged.sql_transaction, Some(false));
+
+        assert!(config.resolve(Some("missing")).is_err());
+        assert!(ProjectConfig::from_str("[options]\nsql_dialcet = \"mysql\"\n").is_err());
+        assert!(ProjectConfig::from_str("shet = \"data\"\n").is_err());
+    }
+}
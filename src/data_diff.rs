@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tera::Context;
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::template::render_html_report;
+use crate::models::{CellValue, ExcelData, ExcelRow};
+
+/// 单元格的变化
+#[derive(Debug, Clone, Serialize)]
+pub struct CellChange {
+    /// 列名
+    pub column: String,
+    /// 原值，新增行为空
+    pub before: CellValue,
+    /// 新值，删除行为空
+    pub after: CellValue,
+}
+
+/// 行的变化
+#[derive(Debug, Clone, Serialize)]
+pub struct RowDiff {
+    /// 主键值，顺序与主键列一致
+    pub key: Vec<String>,
+    /// 主键的原始值（删除的行取旧数据，其余取新数据），用于生成 SQL
+    #[serde(skip)]
+    pub key_values: Vec<CellValue>,
+    /// 单元格变化；新增和删除的行包含整行的值
+    pub changes: Vec<CellChange>,
+}
+
+/// 按主键对比两份数据的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDiff {
+    /// 主键列
+    pub key_columns: Vec<String>,
+    /// 两份数据都有、参与对比的列
+    pub columns: Vec<String>,
+    /// 只在新数据中出现的列
+    pub added_columns: Vec<String>,
+    /// 只在旧数据中出现的列
+    pub removed_columns: Vec<String>,
+    /// 新增的行
+    pub added: Vec<RowDiff>,
+    /// 删除的行
+    pub removed: Vec<RowDiff>,
+    /// 修改的行，只包含有变化的单元格
+    pub modified: Vec<RowDiff>,
+}
+
+/// HTML 报告中的一行
+#[derive(Serialize)]
+struct ReportRow<'a> {
+    kind: &'static str,
+    key: String,
+    column: &'a str,
+    before: String,
+    after: String,
+}
+
+impl DataDiff {
+    /// 按主键列对比旧数据和新数据
+    ///
+    /// 单元格按显示文本比较，数值 `1` 与文本 `"1"` 视为相同；
+    /// 只在一侧出现的列不参与修改判断，单独记录在 `added_columns` / `removed_columns` 中
+    pub fn compare(old: &ExcelData, new: &ExcelData, keys: &[String]) -> Result<Self> {
+        if keys.is_empty() {
+            return Err(ExcelCliError::ExportError(
+                "对比数据需要指定主键列 (--key)".to_string(),
+            ));
+        }
+        for (data, side) in [(old, "旧数据"), (new, "新数据")] {
+            if let Some(key) = keys.iter().find(|k| !data.headers.contains(k)) {
+                return Err(ExcelCliError::ExportError(format!(
+                    "主键列 '{}' 在{}中不存在",
+                    key, side
+                )));
+            }
+        }
+
+        let columns: Vec<String> = new
+            .headers
+            .iter()
+            .filter(|h| old.headers.contains(h))
+            .cloned()
+            .collect();
+        let added_columns = new
+            .headers
+            .iter()
+            .filter(|h| !old.headers.contains(h))
+            .cloned()
+            .collect();
+        let removed_columns = old
+            .headers
+            .iter()
+            .filter(|h| !new.headers.contains(h))
+            .cloned()
+            .collect();
+
+        let old_rows = index_rows(old, keys, "旧数据")?;
+        let new_keys = index_rows(new, keys, "新数据")?;
+
+        let mut added = Vec::new();
+        let mut modified = Vec::new();
+        for (key, row) in new.rows.iter().map(|row| (row_key(row, keys), row)) {
+            match old_rows.get(&key) {
+                None => added.push(RowDiff {
+                    changes: whole_row(&new.headers, row, false),
+                    key_values: key_values(row, keys),
+                    key,
+                }),
+                Some(old_row) => {
+                    let changes: Vec<CellChange> = columns
+                        .iter()
+                        .filter(|c| !keys.contains(c))
+                        .filter_map(|column| {
+                            let before = cell(old_row, column);
+                            let after = cell(row, column);
+                            (before.to_string() != after.to_string()).then(|| CellChange {
+                                column: column.clone(),
+                                before,
+                                after,
+                            })
+                        })
+                        .collect();
+                    if !changes.is_empty() {
+                        modified.push(RowDiff {
+                            key,
+                            key_values: key_values(row, keys),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+
+        let removed = old
+            .rows
+            .iter()
+            .map(|row| (row_key(row, keys), row))
+            .filter(|(key, _)| !new_keys.contains_key(key))
+            .map(|(key, row)| RowDiff {
+                changes: whole_row(&old.headers, row, true),
+                key_values: key_values(row, keys),
+                key,
+            })
+            .collect();
+
+        Ok(Self {
+            key_columns: keys.to_vec(),
+            columns,
+            added_columns,
+            removed_columns,
+            added,
+            removed,
+            modified,
+        })
+    }
+
+    /// 是否没有任何行变化
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// 按 (变更类型, 行) 顺序遍历所有变化：先删除，再修改，最后新增
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, &RowDiff)> {
+        self.removed
+            .iter()
+            .map(|row| ("removed", row))
+            .chain(self.modified.iter().map(|row| ("modified", row)))
+            .chain(self.added.iter().map(|row| ("added", row)))
+    }
+
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 输出为 CSV，每个单元格变化一行：`change, 主键列..., column, before, after`
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let mut header = vec!["change".to_string()];
+        header.extend(self.key_columns.iter().cloned());
+        header.extend(["column", "before", "after"].map(String::from));
+        writer.write_record(&header)?;
+
+        for (kind, row) in self.entries() {
+            for change in &row.changes {
+                let mut record = vec![kind.to_string()];
+                record.extend(row.key.iter().cloned());
+                record.push(change.column.clone());
+                record.push(change.before.to_string());
+                record.push(change.after.to_string());
+                writer.write_record(&record)?;
+            }
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| ExcelCliError::ExportError(format!("CSV 写入失败: {}", e)))?;
+        String::from_utf8(bytes).map_err(|e| ExcelCliError::ExportError(e.to_string()))
+    }
+
+    /// 渲染为 HTML 报告
+    pub fn to_html(&self) -> Result<String> {
+        let rows: Vec<ReportRow> = self
+            .entries()
+            .flat_map(|(kind, row)| {
+                let key = row.key.join(", ");
+                row.changes.iter().map(move |change| ReportRow {
+                    kind,
+                    key: key.clone(),
+                    column: &change.column,
+                    before: change.before.to_string(),
+                    after: change.after.to_string(),
+                })
+            })
+            .collect();
+
+        let mut context = Context::new();
+        context.insert("key_columns", &self.key_columns);
+        context.insert("added_columns", &self.added_columns);
+        context.insert("removed_columns", &self.removed_columns);
+        context.insert("added", &self.added.len());
+        context.insert("removed", &self.removed.len());
+        context.insert("modified", &self.modified.len());
+        context.insert("rows", &rows);
+        render_html_report(
+            "data_diff",
+            include_str!("../templates/data_diff.tera"),
+            &context,
+        )
+    }
+}
+
+/// 行的主键值
+fn row_key(row: &ExcelRow, keys: &[String]) -> Vec<String> {
+    keys.iter().map(|k| cell(row, k).to_string()).collect()
+}
+
+/// 行的主键单元格
+fn key_values(row: &ExcelRow, keys: &[String]) -> Vec<CellValue> {
+    keys.iter().map(|k| cell(row, k)).collect()
+}
+
+/// 单元格的值，缺失时为空
+fn cell(row: &ExcelRow, column: &str) -> CellValue {
+    row.data.get(column).cloned().unwrap_or(CellValue::Empty)
+}
+
+/// 按主键建立索引，主键重复时报错
+fn index_rows<'a>(
+    data: &'a ExcelData,
+    keys: &[String],
+    side: &str,
+) -> Result<HashMap<Vec<String>, &'a ExcelRow>> {
+    let mut index = HashMap::new();
+    for row in &data.rows {
+        let key = row_key(row, keys);
+        if index.insert(key.clone(), row).is_some() {
+            return Err(ExcelCliError::ExportError(format!(
+                "{}中主键重复: ({})",
+                side,
+                key.join(", ")
+            )));
+        }
+    }
+    Ok(index)
+}
+
+/// 新增或删除的行的全部单元格
+fn whole_row(headers: &[String], row: &ExcelRow, removed: bool) -> Vec<CellChange> {
+    headers
+        .iter()
+        .map(|column| {
+            let value = cell(row, column);
+            let (before, after) = if removed {
+                (value, CellValue::Empty)
+            } else {
+                (CellValue::Empty, value)
+            };
+            CellChange {
+                column: column.clone(),
+                before,
+                after,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_by_key() {
+        let old = ExcelData::from_rows(
+            "items",
+            &["ID", "Name", "Price", "Legacy"],
+            &[
+                &[
+                    CellValue::Number(1.0),
+                    CellValue::text("Pen"),
+                    CellValue::Number(2.5),
+                    CellValue::text("x"),
+                ],
+                &[
+                    CellValue::Number(2.0),
+                    CellValue::text("Ink"),
+                    CellValue::Number(9.0),
+                    CellValue::text("y"),
+                ],
+                &[
+                    CellValue::Number(3.0),
+                    CellValue::text("Pad"),
+                    CellValue::Number(4.0),
+                    CellValue::text("z"),
+                ],
+            ],
+        );
+        let new = ExcelData::from_rows(
+            "items",
+            &["ID", "Name", "Price", "Stock"],
+            &[
+                &[
+                    CellValue::text("1"),
+                    CellValue::text("Pen"),
+                    CellValue::Number(2.5),
+                    CellValue::Number(10.0),
+                ],
+                &[
+                    CellValue::Number(3.0),
+                    CellValue::text("Pad"),
+                    CellValue::Number(4.5),
+                    CellValue::Empty,
+                ],
+                &[
+                    CellValue::Number(4.0),
+                    CellValue::text("Cap"),
+                    CellValue::Number(1.0),
+                    CellValue::Empty,
+                ],
+            ],
+        );
+
+        let diff = DataDiff::compare(&old, &new, &["ID".to_string()]).unwrap();
+        assert_eq!(diff.columns, vec!["ID", "Name", "Price"]);
+        assert_eq!(diff.added_columns, vec!["Stock"]);
+        assert_eq!(diff.removed_columns, vec!["Legacy"]);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, vec!["4"]);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].key, vec!["2"]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].key, vec!["3"]);
+        assert_eq!(diff.modified[0].changes.len(), 1);
+        assert_eq!(diff.modified[0].changes[0].column, "Price");
+        assert_eq!(diff.modified[0].changes[0].after.to_string(), "4.5");
+
+        let csv = diff.to_csv().unwrap();
+        assert!(csv.starts_with("change,ID,column,before,after\nremoved,2,ID,2,\n"));
+        assert!(csv.contains("modified,3,Price,4,4.5\n"));
+        assert!(csv.ends_with("added,4,Stock,,\n"));
+
+        let json = diff.to_json().unwrap();
+        assert!(json.contains("\"before\": 4.0"));
+        assert!(diff.to_html().unwrap().contains("<td>Price</td>"));
+    }
+
+    #[test]
+    fn test_compare_errors() {
+        let old = ExcelData::from_rows(
+            "items",
+            &["ID", "Name"],
+            &[
+                &[CellValue::Number(1.0), CellValue::text("a")],
+                &[CellValue::Number(1.0), CellValue::text("b")],
+            ],
+        );
+        let new = ExcelData::from_rows("items", &["ID", "Name"], &[]);
+        assert!(DataDiff::compare(&old, &new, &["ID".to_string()]).is_err());
+        assert!(DataDiff::compare(&new, &new, &["Code".to_string()]).is_err());
+        assert!(DataDiff::compare(&new, &new, &[]).is_err());
+        assert!(DataDiff::compare(&new, &new, &["ID".to_string()])
+            .unwrap()
+            .is_empty());
+    }
+}
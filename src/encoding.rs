@@ -0,0 +1,376 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use encoding_rs::{Encoder, EncoderResult, Encoding};
+
+use crate::error::{ExcelCliError, Result};
+
+/// 输出字符编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputEncoding {
+    /// UTF-8（默认）
+    Utf8,
+    /// UTF-16 小端序（带 BOM）
+    Utf16Le,
+    /// UTF-16 大端序（带 BOM）
+    Utf16Be,
+    /// 传统编码，如 GBK、Shift_JIS、Big5
+    Legacy(&'static Encoding),
+}
+
+impl OutputEncoding {
+    /// 从字符串解析编码，支持 WHATWG 编码标签（gbk、shift_jis、big5 等）
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => return Ok(OutputEncoding::Utf8),
+            "utf-16" | "utf16" | "utf-16le" | "utf16le" => return Ok(OutputEncoding::Utf16Le),
+            "utf-16be" | "utf16be" => return Ok(OutputEncoding::Utf16Be),
+            "sjis" | "cp932" => return Ok(OutputEncoding::Legacy(encoding_rs::SHIFT_JIS)),
+            "cp936" => return Ok(OutputEncoding::Legacy(encoding_rs::GBK)),
+            _ => {}
+        }
+
+        match Encoding::for_label(s.trim().as_bytes()) {
+            // 只接受可以作为输出编码的编码（排除 replacement 等）
+            Some(encoding) if encoding.output_encoding() == encoding => {
+                if encoding == encoding_rs::UTF_8 {
+                    Ok(OutputEncoding::Utf8)
+                } else {
+                    Ok(OutputEncoding::Legacy(encoding))
+                }
+            }
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的字符编码: {}",
+                s
+            ))),
+        }
+    }
+
+    /// 获取编码名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputEncoding::Utf8 => "UTF-8",
+            OutputEncoding::Utf16Le => "UTF-16LE",
+            OutputEncoding::Utf16Be => "UTF-16BE",
+            OutputEncoding::Legacy(encoding) => encoding.name(),
+        }
+    }
+
+    /// 是否为 Unicode 编码（可以表示任意字符）
+    pub fn is_unicode(&self) -> bool {
+        !matches!(self, OutputEncoding::Legacy(_))
+    }
+}
+
+/// 遇到目标编码无法表示的字符时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmappableAction {
+    /// 报错并终止导出
+    Fail,
+    /// 替换为 `?`
+    Replace,
+}
+
+impl UnmappableAction {
+    /// 从字符串解析处理方式
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fail" | "strict" | "error" => Ok(UnmappableAction::Fail),
+            "replace" => Ok(UnmappableAction::Replace),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的编码错误处理方式: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 文本编码器，所有文本导出器在写文件前通过它转换编码
+#[derive(Debug, Clone, Copy)]
+pub struct TextEncoder {
+    encoding: OutputEncoding,
+    on_unmappable: UnmappableAction,
+    bom: bool,
+}
+
+impl TextEncoder {
+    /// 创建新的文本编码器
+    pub fn new(encoding: OutputEncoding) -> Self {
+        Self {
+            encoding,
+            on_unmappable: UnmappableAction::Fail,
+            bom: false,
+        }
+    }
+
+    /// 设置无法表示字符时的处理方式
+    pub fn with_unmappable(mut self, action: UnmappableAction) -> Self {
+        self.on_unmappable = action;
+        self
+    }
+
+    /// 设置是否写入 BOM（UTF-16 总是写入 BOM）
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// 获取输出编码
+    pub fn encoding(&self) -> OutputEncoding {
+        self.encoding
+    }
+
+    /// 将文本转换为目标编码的字节
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut writer = self.writer(Vec::new())?;
+        let result = writer.write_all(text.as_bytes()).map_err(Into::into);
+        writer.finish(result)?;
+        Ok(writer.into_inner())
+    }
+
+    /// 创建按目标编码写入 `inner` 的流式写入器，需要时先写入 BOM
+    pub fn writer<W: Write>(&self, mut inner: W) -> Result<EncodingWriter<W>> {
+        let legacy = match self.encoding {
+            OutputEncoding::Utf8 if self.bom => {
+                inner.write_all(b"\xEF\xBB\xBF")?;
+                None
+            }
+            OutputEncoding::Utf8 => None,
+            OutputEncoding::Utf16Le => {
+                inner.write_all(&[0xFF, 0xFE])?;
+                None
+            }
+            OutputEncoding::Utf16Be => {
+                inner.write_all(&[0xFE, 0xFF])?;
+                None
+            }
+            OutputEncoding::Legacy(encoding) => {
+                if self.bom {
+                    return Err(ExcelCliError::EncodingError(format!(
+                        "{} 编码不支持 BOM",
+                        encoding.name()
+                    )));
+                }
+                Some(encoding.new_encoder())
+            }
+        };
+        Ok(EncodingWriter {
+            inner,
+            encoding: self.encoding,
+            on_unmappable: self.on_unmappable,
+            legacy,
+            pending: Vec::new(),
+            output: Vec::new(),
+            line: 1,
+            error: None,
+        })
+    }
+
+    /// 创建文件，通过 `write` 以 UTF-8 写入内容，边写边转换为目标编码
+    pub fn write_with<F>(&self, path: &str, write: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn Write) -> Result<()>,
+    {
+        let mut writer = self.writer(BufWriter::new(File::create(path)?))?;
+        let result = write(&mut writer);
+        writer.finish(result)
+    }
+
+    /// 将文本按目标编码写入文件
+    pub fn write_file(&self, path: &str, text: &str) -> Result<()> {
+        self.write_with(path, |out| Ok(out.write_all(text.as_bytes())?))
+    }
+}
+
+/// 流式编码写入器：接收 UTF-8 字节，转换为目标编码后写入底层写入器
+///
+/// 跨越两次写入的多字节字符会被暂存，内存占用与输出大小无关
+pub struct EncodingWriter<W: Write> {
+    inner: W,
+    encoding: OutputEncoding,
+    on_unmappable: UnmappableAction,
+    legacy: Option<Encoder>,
+    /// 尚未凑成完整字符的 UTF-8 字节
+    pending: Vec<u8>,
+    output: Vec<u8>,
+    /// 当前行号，用于错误信息
+    line: usize,
+    /// 编码失败的原因，`io::Write` 只能返回 I/O 错误，原始错误在这里保留
+    error: Option<ExcelCliError>,
+}
+
+impl<W: Write> EncodingWriter<W> {
+    /// 结束写入：`result` 为写入过程的结果，编码失败时返回编码错误而不是 I/O 错误
+    pub fn finish(&mut self, result: Result<()>) -> Result<()> {
+        let result = result.and_then(|_| {
+            if !self.pending.is_empty() {
+                return Err(ExcelCliError::EncodingError(
+                    "输出内容不是有效的 UTF-8 文本".to_string(),
+                ));
+            }
+            self.encode_text("", true)?;
+            self.inner.flush()?;
+            Ok(())
+        });
+        match (result, self.error.take()) {
+            (Err(_), Some(error)) => Err(error),
+            (result, _) => result,
+        }
+    }
+
+    /// 取出底层写入器
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// 转换一段完整的文本并写入底层写入器
+    fn encode_text(&mut self, text: &str, last: bool) -> Result<()> {
+        self.output.clear();
+        match self.encoding {
+            OutputEncoding::Utf8 => self.output.extend_from_slice(text.as_bytes()),
+            OutputEncoding::Utf16Le => {
+                for unit in text.encode_utf16() {
+                    self.output.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            OutputEncoding::Utf16Be => {
+                for unit in text.encode_utf16() {
+                    self.output.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+            OutputEncoding::Legacy(encoding) => {
+                let encoder = self.legacy.as_mut().expect("传统编码需要编码器");
+                let mut remaining = text;
+                self.output.reserve(text.len() + 16);
+                loop {
+                    let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(
+                        remaining,
+                        &mut self.output,
+                        last,
+                    );
+                    remaining = &remaining[read..];
+
+                    match result {
+                        EncoderResult::InputEmpty => break,
+                        EncoderResult::OutputFull => self.output.reserve(remaining.len() + 16),
+                        EncoderResult::Unmappable(c) => match self.on_unmappable {
+                            UnmappableAction::Replace => self.output.push(b'?'),
+                            UnmappableAction::Fail => {
+                                let consumed = text.len() - remaining.len();
+                                let line = self.line + text[..consumed].matches('\n').count();
+                                return Err(ExcelCliError::EncodingError(format!(
+                                    "第 {} 行的字符 '{}' (U+{:04X}) 无法用 {} 编码表示",
+                                    line,
+                                    c,
+                                    c as u32,
+                                    encoding.name()
+                                )));
+                            }
+                        },
+                    }
+                }
+            }
+        }
+        self.line += text.matches('\n').count();
+        self.inner.write_all(&self.output)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            // 末尾是不完整的字符，等待下一次写入
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "输出内容不是有效的 UTF-8 文本",
+                ))
+            }
+        };
+        let pending = std::mem::take(&mut self.pending);
+        // 前 valid 个字节已验证为 UTF-8
+        let text = std::str::from_utf8(&pending[..valid]).unwrap_or_default();
+        if let Err(error) = self.encode_text(text, false) {
+            let message = error.to_string();
+            self.error = Some(error);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+        }
+        self.pending = pending[valid..].to_vec();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Default for TextEncoder {
+    fn default() -> Self {
+        Self::new(OutputEncoding::Utf8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encoding() {
+        assert_eq!(
+            OutputEncoding::from_str("utf-8").unwrap(),
+            OutputEncoding::Utf8
+        );
+        assert_eq!(OutputEncoding::from_str("GBK").unwrap().name(), "GBK");
+        assert_eq!(
+            OutputEncoding::from_str("shift_jis").unwrap().name(),
+            "Shift_JIS"
+        );
+        assert_eq!(
+            OutputEncoding::from_str("UTF-16").unwrap(),
+            OutputEncoding::Utf16Le
+        );
+        assert!(OutputEncoding::from_str("no-such-encoding").is_err());
+    }
+
+    #[test]
+    fn test_encode_gbk() {
+        let encoder = TextEncoder::new(OutputEncoding::from_str("gbk").unwrap());
+        assert_eq!(encoder.encode("中文,a").unwrap(), b"\xD6\xD0\xCE\xC4,a");
+
+        // GBK 无法表示 emoji
+        let err = encoder.encode("a\nb😀").unwrap_err();
+        assert!(err.to_string().contains("第 2 行"));
+
+        let replacing = encoder.with_unmappable(UnmappableAction::Replace);
+        assert_eq!(replacing.encode("b😀").unwrap(), b"b?");
+
+        // 多字节字符被拆到两次写入中
+        let mut writer = encoder.writer(Vec::new()).unwrap();
+        let bytes = "a\n中文".as_bytes();
+        writer.write_all(&bytes[..3]).unwrap();
+        writer.write_all(&bytes[3..]).unwrap();
+        writer.finish(Ok(())).unwrap();
+        assert_eq!(writer.into_inner(), b"a\n\xD6\xD0\xCE\xC4");
+
+        let mut writer = encoder.writer(Vec::new()).unwrap();
+        let result = writer.write_all("a\nb\n😀".as_bytes()).map_err(Into::into);
+        let err = writer.finish(result).unwrap_err();
+        assert!(matches!(err, ExcelCliError::EncodingError(_)));
+        assert!(err.to_string().contains("第 3 行"));
+    }
+
+    #[test]
+    fn test_encode_utf16_with_bom() {
+        let encoder = TextEncoder::new(OutputEncoding::Utf16Be);
+        assert_eq!(encoder.encode("A").unwrap(), vec![0xFE, 0xFF, 0x00, 0x41]);
+
+        let utf8 = TextEncoder::default().with_bom(true);
+        assert_eq!(utf8.encode("A").unwrap(), b"\xEF\xBB\xBFA");
+    }
+}
@@ -31,6 +31,14 @@ pub enum ExcelCliError {
     #[error("Excel 处理失败: {0}")]
     CalamineError(#[from] calamine::Error),
 
+    /// SQLite 错误
+    #[error("SQLite 操作失败: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+
+    /// 字符编码错误
+    #[error("字符编码失败: {0}")]
+    EncodingError(String),
+
     /// 不支持的格式
     #[error("不支持的导出格式: {0}")]
     UnsupportedFormat(String),
@@ -0,0 +1,446 @@
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanBuilder, Date32Builder, Decimal128Builder, Float64Builder, Int64Builder,
+    StringBuilder, TimestampMillisecondBuilder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::ipc::writer::{FileWriter, IpcWriteOptions};
+use arrow::record_batch::RecordBatch;
+use chrono::NaiveDate;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::Exporter;
+use crate::models::{CellValue, ExcelData};
+use crate::schema::{parse_bool, parse_date, parse_datetime, SqlType, TypeInference};
+
+/// 默认行组（批次）大小
+const DEFAULT_ROW_GROUP_SIZE: usize = 65536;
+
+/// 列式格式压缩算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnarCompression {
+    /// 不压缩
+    None,
+    /// Snappy（仅 Parquet）
+    Snappy,
+    /// Gzip（仅 Parquet）
+    Gzip,
+    /// LZ4
+    Lz4,
+    /// Zstandard
+    Zstd,
+}
+
+impl ColumnarCompression {
+    /// 从字符串解析压缩算法
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(ColumnarCompression::None),
+            "snappy" => Ok(ColumnarCompression::Snappy),
+            "gzip" => Ok(ColumnarCompression::Gzip),
+            "lz4" => Ok(ColumnarCompression::Lz4),
+            "zstd" => Ok(ColumnarCompression::Zstd),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的压缩算法: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 将推断出的 SQL 类型映射为 Arrow 类型
+pub fn arrow_type(sql_type: &SqlType) -> DataType {
+    match sql_type {
+        SqlType::Integer | SqlType::BigInt => DataType::Int64,
+        SqlType::Float | SqlType::Double => DataType::Float64,
+        SqlType::Decimal(precision, scale) => DataType::Decimal128(*precision as u8, *scale as i8),
+        SqlType::Boolean => DataType::Boolean,
+        SqlType::Varchar(_) | SqlType::Text => DataType::Utf8,
+        SqlType::Date => DataType::Date32,
+        SqlType::DateTime => DataType::Timestamp(TimeUnit::Millisecond, None),
+    }
+}
+
+/// 将 Excel 数据转换为 Arrow RecordBatch（列类型来自 TypeInference）
+pub fn to_record_batch(data: &ExcelData) -> Result<RecordBatch> {
+    let mut fields = Vec::with_capacity(data.headers.len());
+    let mut columns = Vec::with_capacity(data.headers.len());
+
+    for header in &data.headers {
+        let data_type = arrow_type(&TypeInference::infer_column_type(data, header));
+        let cells: Vec<&CellValue> = data
+            .rows
+            .iter()
+            .map(|row| row.data.get(header).unwrap_or(&CellValue::Empty))
+            .collect();
+        let array = build_array(&data_type, cells.iter().copied());
+
+        // 非空单元格不能静默变为 null
+        let rejected: Vec<usize> = (0..cells.len())
+            .filter(|&i| array.is_null(i) && !cells[i].is_empty())
+            .collect();
+        if let Some(&first) = rejected.first() {
+            return Err(ExcelCliError::ExportError(format!(
+                "列 '{}' 有 {} 个值无法转换为 {}，如第 {} 行的 '{}'",
+                header,
+                rejected.len(),
+                data_type,
+                first + 1,
+                cells[first].to_string()
+            )));
+        }
+
+        // 存在空值的列标记为可空
+        fields.push(Field::new(header, data_type, array.null_count() > 0));
+        columns.push(array);
+    }
+
+    let schema: SchemaRef = Arc::new(Schema::new(fields));
+    RecordBatch::try_new(schema, columns).map_err(|e| ExcelCliError::ExportError(e.to_string()))
+}
+
+/// 按目标类型构建列数组，无法转换的单元格写入 null（由调用方检查并报错）
+fn build_array<'a>(data_type: &DataType, cells: impl Iterator<Item = &'a CellValue>) -> ArrayRef {
+    match data_type {
+        DataType::Int64 => {
+            let mut builder = Int64Builder::new();
+            for cell in cells {
+                builder.append_option(match cell {
+                    // 超出 i64 范围的值不能截断
+                    CellValue::Number(n) if n.is_finite() && n.abs() < i64::MAX as f64 => {
+                        Some(*n as i64)
+                    }
+                    CellValue::Boolean(b) => Some(*b as i64),
+                    CellValue::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::new();
+            for cell in cells {
+                builder.append_option(match cell {
+                    CellValue::Number(n) => Some(*n),
+                    CellValue::Boolean(b) => Some(*b as i64 as f64),
+                    CellValue::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Decimal128(_, scale) => {
+            let factor = 10f64.powi(*scale as i32);
+            let mut builder = Decimal128Builder::new().with_data_type(data_type.clone());
+            for cell in cells {
+                builder.append_option(match cell {
+                    CellValue::Number(n) if n.is_finite() => Some((n * factor).round() as i128),
+                    CellValue::Boolean(b) => Some(*b as i128 * factor as i128),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::new();
+            for cell in cells {
+                builder.append_option(match cell {
+                    CellValue::Boolean(b) => Some(*b),
+                    CellValue::String(s) => parse_bool(s),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Date32 => {
+            let mut builder = Date32Builder::new();
+            for cell in cells {
+                builder.append_option(match cell {
+                    // 读取时日期单元格已转换为 ISO 文本
+                    CellValue::String(s) => parse_date(s).map(|d| {
+                        (d - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as i32
+                    }),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Timestamp(_, _) => {
+            let mut builder = TimestampMillisecondBuilder::new();
+            for cell in cells {
+                builder.append_option(match cell {
+                    CellValue::String(s) => {
+                        parse_datetime(s).map(|dt| dt.and_utc().timestamp_millis())
+                    }
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            for cell in cells {
+                match cell {
+                    CellValue::Empty => builder.append_null(),
+                    value => builder.append_value(value.to_string()),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+/// Parquet 导出器
+pub struct ParquetExporter {
+    row_group_size: usize,
+    compression: ColumnarCompression,
+}
+
+impl ParquetExporter {
+    /// 创建新的 Parquet 导出器（默认 Snappy 压缩）
+    pub fn new() -> Self {
+        Self {
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            compression: ColumnarCompression::Snappy,
+        }
+    }
+
+    /// 设置每个行组的最大行数
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = row_group_size.max(1);
+        self
+    }
+
+    /// 设置压缩算法
+    pub fn with_compression(mut self, compression: ColumnarCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+impl Default for ParquetExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for ParquetExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        let batch = to_record_batch(data)?;
+
+        let compression = match self.compression {
+            ColumnarCompression::None => Compression::UNCOMPRESSED,
+            ColumnarCompression::Snappy => Compression::SNAPPY,
+            ColumnarCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ColumnarCompression::Lz4 => Compression::LZ4_RAW,
+            ColumnarCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        };
+        let props = WriterProperties::builder()
+            .set_compression(compression)
+            .set_max_row_group_size(self.row_group_size)
+            .build();
+
+        let file = File::create(output_path)?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props))
+            .map_err(|e| ExcelCliError::ExportError(format!("Parquet 写入失败: {}", e)))?;
+        writer
+            .write(&batch)
+            .and_then(|_| writer.close().map(|_| ()))
+            .map_err(|e| ExcelCliError::ExportError(format!("Parquet 写入失败: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Parquet"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "parquet"
+    }
+}
+
+/// Arrow IPC 文件导出器
+pub struct ArrowExporter {
+    batch_size: usize,
+    compression: ColumnarCompression,
+}
+
+impl ArrowExporter {
+    /// 创建新的 Arrow IPC 导出器（默认不压缩）
+    pub fn new() -> Self {
+        Self {
+            batch_size: DEFAULT_ROW_GROUP_SIZE,
+            compression: ColumnarCompression::None,
+        }
+    }
+
+    /// 设置每个 RecordBatch 的最大行数
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// 设置压缩算法（Arrow IPC 仅支持 lz4 和 zstd）
+    pub fn with_compression(mut self, compression: ColumnarCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+impl Default for ArrowExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for ArrowExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        let batch = to_record_batch(data)?;
+
+        let compression = match self.compression {
+            ColumnarCompression::None => None,
+            ColumnarCompression::Lz4 => Some(arrow::ipc::CompressionType::LZ4_FRAME),
+            ColumnarCompression::Zstd => Some(arrow::ipc::CompressionType::ZSTD),
+            other => {
+                return Err(ExcelCliError::ExportError(format!(
+                    "Arrow IPC 不支持 {:?} 压缩，可选: none, lz4, zstd",
+                    other
+                )))
+            }
+        };
+        let options = IpcWriteOptions::default()
+            .try_with_compression(compression)
+            .map_err(|e| ExcelCliError::ExportError(e.to_string()))?;
+
+        let file = File::create(output_path)?;
+        let mut writer = FileWriter::try_new_with_options(file, &batch.schema(), options)
+            .map_err(|e| ExcelCliError::ExportError(format!("Arrow 写入失败: {}", e)))?;
+
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let length = self.batch_size.min(batch.num_rows() - offset);
+            writer
+                .write(&batch.slice(offset, length))
+                .map_err(|e| ExcelCliError::ExportError(format!("Arrow 写入失败: {}", e)))?;
+            offset += length;
+        }
+
+        writer
+            .finish()
+            .map_err(|e| ExcelCliError::ExportError(format!("Arrow 写入失败: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Arrow"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "arrow"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExcelRow;
+    use std::collections::HashMap;
+
+    fn sample_data() -> ExcelData {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["Name".to_string(), "Age".to_string(), "Score".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert("Name".to_string(), CellValue::String("Alice".to_string()));
+        row1.insert("Age".to_string(), CellValue::Number(30.0));
+        row1.insert("Score".to_string(), CellValue::Number(9.5));
+        data.add_row(ExcelRow { data: row1 });
+
+        let mut row2 = HashMap::new();
+        row2.insert("Name".to_string(), CellValue::String("Bob".to_string()));
+        row2.insert("Age".to_string(), CellValue::Empty);
+        row2.insert("Score".to_string(), CellValue::Number(7.0));
+        data.add_row(ExcelRow { data: row2 });
+
+        data
+    }
+
+    #[test]
+    fn test_record_batch_types() {
+        let batch = to_record_batch(&sample_data()).unwrap();
+        let schema = batch.schema();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        assert!(!schema.field(0).is_nullable());
+        assert_eq!(schema.field(1).data_type(), &DataType::Int64);
+        assert!(schema.field(1).is_nullable());
+        assert_eq!(schema.field(2).data_type(), &DataType::Decimal128(4, 1));
+    }
+
+    #[test]
+    fn test_parquet_and_arrow_export() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let parquet_path = dir.path().join("out.parquet");
+        ParquetExporter::new()
+            .with_compression(ColumnarCompression::Zstd)
+            .with_row_group_size(1)
+            .export(&sample_data(), parquet_path.to_str().unwrap())
+            .unwrap();
+        let bytes = std::fs::read(&parquet_path).unwrap();
+        assert!(bytes.starts_with(b"PAR1"));
+
+        let arrow_path = dir.path().join("out.arrow");
+        ArrowExporter::new()
+            .export(&sample_data(), arrow_path.to_str().unwrap())
+            .unwrap();
+        let file = File::open(&arrow_path).unwrap();
+        let reader = arrow::ipc::reader::FileReader::try_new(file, None).unwrap();
+        let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, 2);
+
+        assert!(ArrowExporter::new()
+            .with_compression(ColumnarCompression::Snappy)
+            .export(&sample_data(), arrow_path.to_str().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_unconvertible_values_fail() {
+        let mut data = ExcelData::new("Sheet1".to_string(), vec!["Big".to_string()]);
+        for value in [CellValue::Boolean(true), CellValue::Number(1e20)] {
+            let mut row = HashMap::new();
+            row.insert("Big".to_string(), value);
+            data.add_row(ExcelRow { data: row });
+        }
+        let err = to_record_batch(&data).unwrap_err().to_string();
+        assert!(err.contains("列 'Big' 有 1 个值无法转换为 Int64，如第 2 行"));
+
+        // 布尔值按 0/1 写入数值列
+        data.rows.pop();
+        let batch = to_record_batch(&data).unwrap();
+        assert_eq!(batch.column(0).null_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        assert_eq!(
+            parse_date("2024-03-01"),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+        assert!(parse_datetime("2024-03-01 12:30:00").is_some());
+        assert!(parse_datetime("2024-03-01").is_some());
+        assert!(parse_date("not a date").is_none());
+    }
+}
@@ -1,18 +1,124 @@
-use std::fs::File;
+use std::io::Write;
 
-use crate::error::Result;
+use crate::encoding::TextEncoder;
+use crate::error::{ExcelCliError, Result};
 use crate::exporter::Exporter;
-use crate::models::ExcelData;
+use crate::models::{CellValue, ExcelData};
+
+/// CSV 引号策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvQuoteStyle {
+    /// 所有字段都加引号
+    Always,
+    /// 仅在必要时加引号（包含分隔符、引号或换行）
+    Necessary,
+    /// 从不加引号
+    Never,
+    /// 非数值字段都加引号
+    NonNumeric,
+}
+
+impl CsvQuoteStyle {
+    /// 从字符串解析引号策略
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "always" | "all" => Ok(CsvQuoteStyle::Always),
+            "necessary" | "minimal" => Ok(CsvQuoteStyle::Necessary),
+            "never" | "none" => Ok(CsvQuoteStyle::Never),
+            "non-numeric" | "non_numeric" | "nonnumeric" => Ok(CsvQuoteStyle::NonNumeric),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的 CSV 引号策略: {}",
+                s
+            ))),
+        }
+    }
+
+    fn to_csv(self) -> csv::QuoteStyle {
+        match self {
+            CsvQuoteStyle::Always => csv::QuoteStyle::Always,
+            CsvQuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            CsvQuoteStyle::Never => csv::QuoteStyle::Never,
+            CsvQuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+        }
+    }
+}
+
+/// CSV 行结束符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineTerminator {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+impl LineTerminator {
+    /// 从字符串解析行结束符
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "lf" | "\\n" | "unix" => Ok(LineTerminator::Lf),
+            "crlf" | "\\r\\n" | "windows" => Ok(LineTerminator::Crlf),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的行结束符: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 解析单字节分隔符/转义字符，支持 `tab`、`\t` 等别名
+pub fn parse_csv_byte(s: &str) -> Result<u8> {
+    let value = match s.to_lowercase().as_str() {
+        "tab" | "\\t" | "\t" => "\t",
+        "comma" => ",",
+        "semicolon" => ";",
+        "pipe" => "|",
+        "space" => " ",
+        _ => s,
+    };
+
+    if value.len() == 1 && value.is_ascii() {
+        Ok(value.as_bytes()[0])
+    } else {
+        Err(ExcelCliError::ExportError(format!(
+            "CSV 分隔符/转义字符必须是单个 ASCII 字符: {}",
+            s
+        )))
+    }
+}
 
 /// CSV 导出器
 pub struct CsvExporter {
     delimiter: u8,
+    quote_style: CsvQuoteStyle,
+    escape: Option<u8>,
+    terminator: LineTerminator,
+    has_header: bool,
+    null_as: String,
+    bom: bool,
+    encoder: TextEncoder,
 }
 
 impl CsvExporter {
     /// 创建新的 CSV 导出器
     pub fn new() -> Self {
-        Self { delimiter: b',' }
+        Self {
+            delimiter: b',',
+            quote_style: CsvQuoteStyle::Necessary,
+            escape: None,
+            terminator: LineTerminator::Lf,
+            has_header: true,
+            null_as: String::new(),
+            bom: false,
+            encoder: TextEncoder::default(),
+        }
+    }
+
+    /// 创建 TSV（制表符分隔）导出器
+    pub fn tsv() -> Self {
+        Self::new().with_delimiter(b'\t')
     }
 
     /// 设置分隔符
@@ -20,6 +126,66 @@ impl CsvExporter {
         self.delimiter = delimiter;
         self
     }
+
+    /// 设置引号策略
+    pub fn with_quote_style(mut self, quote_style: CsvQuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// 设置转义字符（默认通过双写引号转义）
+    pub fn with_escape(mut self, escape: u8) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// 设置行结束符
+    pub fn with_terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// 设置是否输出表头
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// 设置空值的输出文本
+    pub fn with_null_as(mut self, null_as: String) -> Self {
+        self.null_as = null_as;
+        self
+    }
+
+    /// 设置是否在文件开头写入 UTF-8 BOM（Windows 上的 Excel 依赖它识别 UTF-8 编码的中文）
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// 设置输出编码
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// 构建底层 CSV 写入器
+    fn build_writer<W: Write>(&self, writer: W) -> csv::Writer<W> {
+        let mut builder = csv::WriterBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote_style(self.quote_style.to_csv())
+            .terminator(match self.terminator {
+                LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+                LineTerminator::Crlf => csv::Terminator::CRLF,
+            });
+
+        if let Some(escape) = self.escape {
+            builder.double_quote(false).escape(escape);
+        }
+
+        builder.from_writer(writer)
+    }
 }
 
 impl Default for CsvExporter {
@@ -30,45 +196,59 @@ impl Default for CsvExporter {
 
 impl Exporter for CsvExporter {
     fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
-        let file = File::create(output_path)?;
-        let mut writer = csv::WriterBuilder::new()
-            .delimiter(self.delimiter)
-            .from_writer(file);
-
-        // 写入表头
-        writer.write_record(&data.headers)?;
-
-        // 写入数据行
-        for row in &data.rows {
-            let mut record = Vec::new();
-            for header in &data.headers {
-                let value = row
-                    .data
-                    .get(header)
-                    .map(|v| v.to_string())
-                    .unwrap_or_default();
-                record.push(value);
+        // 边写边转换编码（BOM 由编码器写入）
+        let encoder = if self.bom {
+            self.encoder.with_bom(true)
+        } else {
+            self.encoder
+        };
+        encoder.write_with(output_path, |out| {
+            let mut writer = self.build_writer(out);
+
+            // 写入表头
+            if self.has_header {
+                writer.write_record(&data.headers)?;
             }
-            writer.write_record(&record)?;
-        }
 
-        writer.flush()?;
-        Ok(())
+            // 写入数据行
+            for row in &data.rows {
+                let mut record = Vec::new();
+                for header in &data.headers {
+                    let value = match row.data.get(header) {
+                        Some(CellValue::Empty) | None => self.null_as.clone(),
+                        Some(v) => v.to_string(),
+                    };
+                    record.push(value);
+                }
+                writer.write_record(&record)?;
+            }
+
+            writer.flush()?;
+            Ok(())
+        })
     }
 
     fn format_name(&self) -> &'static str {
-        "CSV"
+        if self.delimiter == b'\t' {
+            "TSV"
+        } else {
+            "CSV"
+        }
     }
 
     fn file_extension(&self) -> &'static str {
-        "csv"
+        if self.delimiter == b'\t' {
+            "tsv"
+        } else {
+            "csv"
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{CellValue, ExcelRow};
+    use crate::models::ExcelRow;
     use std::collections::HashMap;
 
     #[test]
@@ -90,4 +270,62 @@ mod tests {
         // 清理测试文件
         let _ = std::fs::remove_file("test_output.csv");
     }
+
+    #[test]
+    fn test_csv_dialect_options() {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["Name".to_string(), "Age".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert("Name".to_string(), CellValue::String("张三".to_string()));
+        row1.insert("Age".to_string(), CellValue::Empty);
+        data.add_row(ExcelRow { data: row1 });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let exporter = CsvExporter::new()
+            .with_delimiter(b';')
+            .with_quote_style(CsvQuoteStyle::Always)
+            .with_terminator(LineTerminator::Crlf)
+            .with_header(false)
+            .with_null_as("NULL".to_string())
+            .with_bom(true);
+        exporter.export(&data, path.to_str().unwrap()).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(b"\xEF\xBB\xBF"));
+        let content = String::from_utf8(bytes[3..].to_vec()).unwrap();
+        assert_eq!(content, "\"张三\";\"NULL\"\r\n");
+    }
+
+    #[test]
+    fn test_csv_gbk_encoding() {
+        let mut data = ExcelData::new("Sheet1".to_string(), vec!["城市".to_string()]);
+
+        let mut row1 = HashMap::new();
+        row1.insert("城市".to_string(), CellValue::String("北京".to_string()));
+        data.add_row(ExcelRow { data: row1 });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let encoding = crate::encoding::OutputEncoding::from_str("gbk").unwrap();
+        let exporter = CsvExporter::new().with_encoder(TextEncoder::new(encoding));
+        exporter.export(&data, path.to_str().unwrap()).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        let (decoded, _, had_errors) = encoding_rs::GBK.decode(&bytes);
+        assert!(!had_errors);
+        assert_eq!(decoded, "城市\n北京\n");
+    }
+
+    #[test]
+    fn test_parse_csv_byte() {
+        assert_eq!(parse_csv_byte("tab").unwrap(), b'\t');
+        assert_eq!(parse_csv_byte("\\t").unwrap(), b'\t');
+        assert_eq!(parse_csv_byte("|").unwrap(), b'|');
+        assert!(parse_csv_byte("ab").is_err());
+        assert!(CsvQuoteStyle::from_str("bogus").is_err());
+    }
 }
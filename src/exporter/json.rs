@@ -1,19 +1,21 @@
-use std::fs::File;
-use std::io::Write;
-
-use crate::error::Result;
+use crate::encoding::TextEncoder;
+use crate::error::{ExcelCliError, Result};
 use crate::exporter::Exporter;
 use crate::models::ExcelData;
 
 /// JSON 导出器
 pub struct JsonExporter {
     pretty: bool,
+    encoder: TextEncoder,
 }
 
 impl JsonExporter {
     /// 创建新的 JSON 导出器
     pub fn new() -> Self {
-        Self { pretty: true }
+        Self {
+            pretty: true,
+            encoder: TextEncoder::default(),
+        }
     }
 
     /// 设置是否使用格式化输出
@@ -21,6 +23,12 @@ impl JsonExporter {
         self.pretty = pretty;
         self
     }
+
+    /// 设置输出编码（JSON 只允许 UTF-8 或 UTF-16）
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
 }
 
 impl Default for JsonExporter {
@@ -31,16 +39,21 @@ impl Default for JsonExporter {
 
 impl Exporter for JsonExporter {
     fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
-        let json_string = if self.pretty {
-            serde_json::to_string_pretty(&data.rows)?
-        } else {
-            serde_json::to_string(&data.rows)?
-        };
-
-        let mut file = File::create(output_path)?;
-        file.write_all(json_string.as_bytes())?;
+        if !self.encoder.encoding().is_unicode() {
+            return Err(ExcelCliError::EncodingError(format!(
+                "JSON 只能使用 UTF-8 或 UTF-16 编码，不支持 {}",
+                self.encoder.encoding().name()
+            )));
+        }
 
-        Ok(())
+        self.encoder.write_with(output_path, |out| {
+            if self.pretty {
+                serde_json::to_writer_pretty(out, &data.rows)?;
+            } else {
+                serde_json::to_writer(out, &data.rows)?;
+            }
+            Ok(())
+        })
     }
 
     fn format_name(&self) -> &'static str {
@@ -77,4 +90,15 @@ mod tests {
         // 清理测试文件
         let _ = std::fs::remove_file("test_output.json");
     }
+
+    #[test]
+    fn test_json_rejects_legacy_encoding() {
+        let data = ExcelData::new("Sheet1".to_string(), vec!["Name".to_string()]);
+        let encoding = crate::encoding::OutputEncoding::from_str("gbk").unwrap();
+        let exporter = JsonExporter::new().with_encoder(TextEncoder::new(encoding));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.json");
+        assert!(exporter.export(&data, path.to_str().unwrap()).is_err());
+    }
 }
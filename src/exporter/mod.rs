@@ -1,13 +1,28 @@
+pub mod columnar;
 pub mod csv;
 pub mod json;
 pub mod sql;
+pub mod sqlite_db;
 pub mod template;
+pub mod xml;
+pub mod yaml;
 
-use crate::error::Result;
+use serde::Deserialize;
+
+use crate::encoding::{OutputEncoding, TextEncoder, UnmappableAction};
+use crate::error::{ExcelCliError, Result};
+use crate::identifier::IdentifierNormalizer;
 use crate::models::ExcelData;
 
-pub use sql::{SqlDialect, SqlExporter, SqlMode};
+pub use columnar::{ArrowExporter, ColumnarCompression, ParquetExporter};
+pub use csv::{CsvExporter, CsvQuoteStyle, LineTerminator};
+pub use sql::{
+    load_snapshot, ClearTable, PreparedStatement, SqlDialect, SqlExporter, SqlMode, SqlParam,
+};
+pub use sqlite_db::SqliteDbExporter;
 pub use template::{BuiltinTemplate, TemplateExporter};
+pub use xml::{XmlExporter, XmlStyle};
+pub use yaml::YamlExporter;
 
 /// 导出器 trait，用于定义导出接口
 ///
@@ -28,6 +43,10 @@ pub trait Exporter {
 }
 
 /// 导出器配置
+///
+/// 可以从任务文件中反序列化，字段名即配置项名称
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ExporterConfig {
     /// SQL 方言（仅用于 SQL 格式）
     pub sql_dialect: Option<String>,
@@ -41,22 +60,163 @@ pub struct ExporterConfig {
     pub update_columns: Option<Vec<String>>,
     /// 列名映射（仅用于 SQL 格式）
     pub column_mapping: Option<Vec<String>>,
+    /// 标识符风格：snake, lower, upper, keep（仅用于 SQL 格式）
+    pub identifier_case: Option<String>,
+    /// 按名称重命名列，每个元素形如 `原列名=新列名`（仅用于 SQL 格式）
+    pub renames: Vec<String>,
+    /// 标识符最大长度，覆盖方言默认值（仅用于 SQL 格式）
+    pub identifier_max_length: Option<usize>,
+    /// 每条 SQL 语句合并的最大行数
+    pub sql_batch_size: Option<usize>,
+    /// 是否用事务包裹 SQL 语句
+    pub sql_transaction: Option<bool>,
+    /// 每 N 条语句提交一次
+    pub sql_commit_every: Option<usize>,
+    /// 是否写入出错即停止的前置语句
+    pub sql_stop_on_error: Option<bool>,
+    /// 加载前清空表的方式 (truncate, delete)
+    pub sql_clear_table: Option<String>,
+    /// 是否允许写入标识列（仅 SQL Server）
+    pub sql_identity_insert: Option<bool>,
+    /// 是否按推断的列类型转换布尔、日期文本（仅用于 SQL 格式）
+    pub sql_normalize_values: Option<bool>,
+    /// 上次同步的快照文件路径（仅用于 SQL sync 模式）
+    pub snapshot: Option<String>,
+    /// 索引列，每个元素为一个索引包含的列（仅用于 sqlite-db 格式）
+    pub indexes: Vec<Vec<String>>,
     /// 自定义模板文件路径（仅用于 template 格式）
     pub template_path: Option<String>,
+    /// 分隔符（仅用于 CSV/TSV 格式）
+    pub csv_delimiter: Option<String>,
+    /// 引号策略：always, necessary, never, non-numeric（仅用于 CSV/TSV 格式）
+    pub csv_quote_style: Option<String>,
+    /// 转义字符，未设置时通过双写引号转义（仅用于 CSV/TSV 格式）
+    pub csv_escape: Option<String>,
+    /// 行结束符：lf 或 crlf（仅用于 CSV/TSV 格式）
+    pub csv_line_terminator: Option<String>,
+    /// 不输出表头（仅用于 CSV/TSV 格式）
+    pub csv_no_header: Option<bool>,
+    /// 空值的输出文本（仅用于 CSV/TSV 格式）
+    pub csv_null_as: Option<String>,
+    /// 写入 UTF-8 BOM（仅用于 CSV/TSV 格式）
+    pub csv_bom: Option<bool>,
+    /// 单元格输出方式：elements 或 attributes（仅用于 XML 格式）
+    pub xml_style: Option<String>,
+    /// 每个行组（RecordBatch）的最大行数（仅用于 Parquet/Arrow 格式）
+    pub row_group_size: Option<usize>,
+    /// 压缩算法：none, snappy, gzip, lz4, zstd（仅用于 Parquet/Arrow 格式）
+    pub compression: Option<String>,
+    /// 输出字符编码，如 utf-8、gbk、shift_jis、utf-16（默认 UTF-8）
+    pub encoding: Option<String>,
+    /// 无法表示的字符的处理方式：fail 或 replace（默认 fail）
+    pub encoding_errors: Option<String>,
 }
 
-impl Default for ExporterConfig {
-    fn default() -> Self {
+impl ExporterConfig {
+    /// 用 `defaults` 补全未设置的选项，已设置的选项保持不变
+    ///
+    /// 用于合并配置文件中的默认值与命令行参数，命令行参数优先
+    pub fn with_defaults(self, defaults: ExporterConfig) -> Self {
+        let ExporterConfig {
+            sql_dialect,
+            sql_table,
+            sql_mode,
+            primary_keys,
+            update_columns,
+            column_mapping,
+            identifier_case,
+            renames,
+            identifier_max_length,
+            sql_batch_size,
+            sql_transaction,
+            sql_commit_every,
+            sql_stop_on_error,
+            sql_clear_table,
+            sql_identity_insert,
+            sql_normalize_values,
+            snapshot,
+            indexes,
+            template_path,
+            csv_delimiter,
+            csv_quote_style,
+            csv_escape,
+            csv_line_terminator,
+            csv_no_header,
+            csv_null_as,
+            csv_bom,
+            xml_style,
+            row_group_size,
+            compression,
+            encoding,
+            encoding_errors,
+        } = defaults;
         Self {
-            sql_dialect: None,
-            sql_table: None,
-            sql_mode: None,
-            primary_keys: None,
-            update_columns: None,
-            column_mapping: None,
-            template_path: None,
+            sql_dialect: self.sql_dialect.or(sql_dialect),
+            sql_table: self.sql_table.or(sql_table),
+            sql_mode: self.sql_mode.or(sql_mode),
+            primary_keys: self.primary_keys.or(primary_keys),
+            update_columns: self.update_columns.or(update_columns),
+            column_mapping: self.column_mapping.or(column_mapping),
+            identifier_case: self.identifier_case.or(identifier_case),
+            renames: if self.renames.is_empty() {
+                renames
+            } else {
+                self.renames
+            },
+            identifier_max_length: self.identifier_max_length.or(identifier_max_length),
+            sql_batch_size: self.sql_batch_size.or(sql_batch_size),
+            sql_transaction: self.sql_transaction.or(sql_transaction),
+            sql_commit_every: self.sql_commit_every.or(sql_commit_every),
+            sql_stop_on_error: self.sql_stop_on_error.or(sql_stop_on_error),
+            sql_clear_table: self.sql_clear_table.or(sql_clear_table),
+            sql_identity_insert: self.sql_identity_insert.or(sql_identity_insert),
+            sql_normalize_values: self.sql_normalize_values.or(sql_normalize_values),
+            snapshot: self.snapshot.or(snapshot),
+            indexes: if self.indexes.is_empty() {
+                indexes
+            } else {
+                self.indexes
+            },
+            template_path: self.template_path.or(template_path),
+            csv_delimiter: self.csv_delimiter.or(csv_delimiter),
+            csv_quote_style: self.csv_quote_style.or(csv_quote_style),
+            csv_escape: self.csv_escape.or(csv_escape),
+            csv_line_terminator: self.csv_line_terminator.or(csv_line_terminator),
+            csv_no_header: self.csv_no_header.or(csv_no_header),
+            csv_null_as: self.csv_null_as.or(csv_null_as),
+            csv_bom: self.csv_bom.or(csv_bom),
+            xml_style: self.xml_style.or(xml_style),
+            row_group_size: self.row_group_size.or(row_group_size),
+            compression: self.compression.or(compression),
+            encoding: self.encoding.or(encoding),
+            encoding_errors: self.encoding_errors.or(encoding_errors),
         }
     }
+
+    /// 检查选项能否用于拆分输出
+    ///
+    /// 拆分后每份文件单独执行：同步和删除模式要按完整数据生成语句，
+    /// 加载前清空表则会让后执行的文件清掉先执行的文件写入的行
+    pub fn check_split(&self, format: &str) -> Result<()> {
+        if !format.eq_ignore_ascii_case("sql") {
+            return Ok(());
+        }
+        if let Some(mode) = &self.sql_mode {
+            let mode = SqlMode::from_str(mode)?;
+            if matches!(mode, SqlMode::Sync | SqlMode::Delete) {
+                return Err(ExcelCliError::ExportError(format!(
+                    "{:?} 模式需要完整的数据，不能拆分输出",
+                    mode
+                )));
+            }
+        }
+        if self.sql_clear_table.is_some() {
+            return Err(ExcelCliError::ExportError(
+                "拆分输出时每份文件都会清空表，请先单独清空表再导入".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// 导出器工厂
@@ -65,15 +225,28 @@ pub struct ExporterFactory;
 impl ExporterFactory {
     /// 根据格式名称和配置创建导出器
     pub fn create(format: &str, config: ExporterConfig) -> Result<Box<dyn Exporter>> {
+        let encoder = Self::create_encoder(&config)?;
+
         match format.to_lowercase().as_str() {
-            "json" => Ok(Box::new(json::JsonExporter::new())),
-            "csv" => Ok(Box::new(csv::CsvExporter::new())),
+            "json" => Ok(Box::new(json::JsonExporter::new().with_encoder(encoder))),
+            "csv" => Ok(Box::new(
+                Self::create_csv(csv::CsvExporter::new(), config)?.with_encoder(encoder),
+            )),
+            "tsv" => Ok(Box::new(
+                Self::create_csv(csv::CsvExporter::tsv(), config)?.with_encoder(encoder),
+            )),
             "sql" => {
                 let dialect_str = config.sql_dialect.as_deref().unwrap_or("mysql");
                 let dialect = SqlDialect::from_str(dialect_str)?;
+                let normalizer = IdentifierNormalizer::from_options(
+                    dialect,
+                    config.identifier_case.as_deref(),
+                    &config.renames,
+                    config.identifier_max_length,
+                )?;
                 let table_name = config.sql_table.unwrap_or_else(|| "table_name".to_string());
 
-                let mut exporter = SqlExporter::new(dialect, table_name);
+                let mut exporter = SqlExporter::new(dialect, table_name).with_encoder(encoder);
 
                 // 设置 SQL 模式
                 if let Some(mode_str) = config.sql_mode {
@@ -96,41 +269,162 @@ impl ExporterFactory {
                     exporter = exporter.with_column_mapping(mapping);
                 }
 
+                // 设置标识符规范化
+                if let Some(normalizer) = normalizer {
+                    exporter = exporter.with_normalizer(normalizer);
+                }
+
+                // 设置批量大小
+                if let Some(batch_size) = config.sql_batch_size {
+                    exporter = exporter.with_batch_size(batch_size);
+                }
+
+                // 设置事务与加载选项
+                exporter = exporter
+                    .with_transaction(config.sql_transaction.unwrap_or(false))
+                    .with_stop_on_error(config.sql_stop_on_error.unwrap_or(false))
+                    .with_identity_insert(config.sql_identity_insert.unwrap_or(false))
+                    .with_normalize_values(config.sql_normalize_values.unwrap_or(false));
+                if let Some(n) = config.sql_commit_every {
+                    exporter = exporter.with_commit_every(n);
+                }
+                if let Some(clear) = config.sql_clear_table {
+                    exporter = exporter.with_clear_table(ClearTable::from_str(&clear)?);
+                }
+
+                // 读取同步快照
+                if let Some(path) = config.snapshot {
+                    exporter = exporter.with_snapshot(load_snapshot(&path)?);
+                }
+
+                Ok(Box::new(exporter))
+            }
+            "sqlite-db" | "sqlite_db" => {
+                let mut exporter = SqliteDbExporter::new();
+                if let Some(table) = config.sql_table {
+                    exporter = exporter.with_table_name(table);
+                }
+                if let Some(keys) = config.primary_keys {
+                    exporter = exporter.with_primary_keys(keys);
+                }
+                for columns in config.indexes {
+                    exporter = exporter.with_index(columns);
+                }
                 Ok(Box::new(exporter))
             }
             "template" => {
                 if let Some(template_path) = config.template_path {
-                    Ok(Box::new(template::TemplateExporter::from_file(
-                        &template_path,
-                    )?))
+                    Ok(Box::new(
+                        template::TemplateExporter::from_file(&template_path)?
+                            .with_encoder(encoder),
+                    ))
                 } else {
                     Err(crate::error::ExcelCliError::ExportError(
                         "Template 格式需要指定 --template 参数".to_string(),
                     ))
                 }
             }
-            "html" | "html-table" => Ok(Box::new(template::TemplateExporter::from_builtin(
-                template::BuiltinTemplate::HtmlTable,
-            ))),
+            "html" | "html-table" => Ok(Box::new(
+                template::TemplateExporter::from_builtin(template::BuiltinTemplate::HtmlTable)
+                    .with_encoder(encoder),
+            )),
             "markdown" | "md" | "md-table" => Ok(Box::new(
-                template::TemplateExporter::from_builtin(template::BuiltinTemplate::MarkdownTable),
+                template::TemplateExporter::from_builtin(template::BuiltinTemplate::MarkdownTable)
+                    .with_encoder(encoder),
             )),
-            "xml" => Ok(Box::new(template::TemplateExporter::from_builtin(
-                template::BuiltinTemplate::Xml,
-            ))),
-            "yaml" | "yml" => Ok(Box::new(template::TemplateExporter::from_builtin(
-                template::BuiltinTemplate::Yaml,
-            ))),
+            "xml" => {
+                let mut exporter = XmlExporter::new().with_encoder(encoder);
+                if let Some(style) = config.xml_style {
+                    exporter = exporter.with_style(XmlStyle::from_str(&style)?);
+                }
+                Ok(Box::new(exporter))
+            }
+            "yaml" | "yml" => Ok(Box::new(YamlExporter::new().with_encoder(encoder))),
+            "parquet" => {
+                let mut exporter = ParquetExporter::new();
+                if let Some(size) = config.row_group_size {
+                    exporter = exporter.with_row_group_size(size);
+                }
+                if let Some(compression) = config.compression {
+                    exporter =
+                        exporter.with_compression(ColumnarCompression::from_str(&compression)?);
+                }
+                Ok(Box::new(exporter))
+            }
+            "arrow" | "ipc" | "feather" => {
+                let mut exporter = ArrowExporter::new();
+                if let Some(size) = config.row_group_size {
+                    exporter = exporter.with_batch_size(size);
+                }
+                if let Some(compression) = config.compression {
+                    exporter =
+                        exporter.with_compression(ColumnarCompression::from_str(&compression)?);
+                }
+                Ok(Box::new(exporter))
+            }
             _ => Err(crate::error::ExcelCliError::UnsupportedFormat(
                 format.to_string(),
             )),
         }
     }
 
+    /// 根据配置创建文本编码器
+    fn create_encoder(config: &ExporterConfig) -> Result<TextEncoder> {
+        let encoding = match config.encoding {
+            Some(ref name) => OutputEncoding::from_str(name)?,
+            None => OutputEncoding::Utf8,
+        };
+
+        let action = match config.encoding_errors {
+            Some(ref action) => UnmappableAction::from_str(action)?,
+            None => UnmappableAction::Fail,
+        };
+
+        Ok(TextEncoder::new(encoding).with_unmappable(action))
+    }
+
+    /// 根据配置设置 CSV/TSV 导出器
+    fn create_csv(mut exporter: CsvExporter, config: ExporterConfig) -> Result<CsvExporter> {
+        if let Some(delimiter) = config.csv_delimiter {
+            exporter = exporter.with_delimiter(csv::parse_csv_byte(&delimiter)?);
+        }
+
+        if let Some(style) = config.csv_quote_style {
+            exporter = exporter.with_quote_style(CsvQuoteStyle::from_str(&style)?);
+        }
+
+        if let Some(escape) = config.csv_escape {
+            exporter = exporter.with_escape(csv::parse_csv_byte(&escape)?);
+        }
+
+        if let Some(terminator) = config.csv_line_terminator {
+            exporter = exporter.with_terminator(LineTerminator::from_str(&terminator)?);
+        }
+
+        if let Some(null_as) = config.csv_null_as {
+            exporter = exporter.with_null_as(null_as);
+        }
+
+        Ok(exporter
+            .with_header(!config.csv_no_header.unwrap_or(false))
+            .with_bom(config.csv_bom.unwrap_or(false)))
+    }
+
     /// 获取所有支持的格式
     pub fn supported_formats() -> Vec<&'static str> {
         vec![
-            "json", "csv", "sql", "template", "html", "markdown", "xml", "yaml",
+            "json",
+            "csv",
+            "tsv",
+            "sql",
+            "sqlite-db",
+            "template",
+            "html",
+            "markdown",
+            "xml",
+            "yaml",
+            "parquet",
+            "arrow",
         ]
     }
 }
@@ -1,9 +1,15 @@
-use std::fs::File;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
+use std::path::Path;
 
+use crate::data_diff::DataDiff;
+use crate::encoding::{OutputEncoding, TextEncoder};
 use crate::error::{ExcelCliError, Result};
 use crate::exporter::Exporter;
-use crate::models::{CellValue, ExcelData};
+use crate::identifier::IdentifierNormalizer;
+use crate::models::{CellValue, ExcelData, ExcelRow};
+use crate::schema::TypeInference;
 
 /// SQL 方言
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +24,14 @@ pub enum SqlDialect {
     SqlServer,
     /// Oracle
     Oracle,
+    /// ClickHouse
+    ClickHouse,
+    /// DuckDB
+    DuckDB,
+    /// Snowflake
+    Snowflake,
+    /// Google BigQuery
+    BigQuery,
 }
 
 /// SQL 语句模式
@@ -29,15 +43,25 @@ pub enum SqlMode {
     Update,
     /// UPSERT 语句（INSERT ... ON CONFLICT/DUPLICATE KEY UPDATE）
     Upsert,
+    /// 批量加载（PostgreSQL COPY FROM stdin / MySQL LOAD DATA LOCAL INFILE）
+    Copy,
+    /// DELETE 语句（按主键删除）
+    Delete,
+    /// 同步：以表格为准，与上次快照对比生成最少的 INSERT/UPDATE/DELETE
+    Sync,
 }
 
 impl SqlMode {
     /// 从字符串解析模式
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "insert" => Ok(SqlMode::Insert),
             "update" => Ok(SqlMode::Update),
             "upsert" | "merge" => Ok(SqlMode::Upsert),
+            "copy" | "load-data" | "load_data" => Ok(SqlMode::Copy),
+            "delete" => Ok(SqlMode::Delete),
+            "sync" => Ok(SqlMode::Sync),
             _ => Err(ExcelCliError::UnsupportedFormat(format!(
                 "不支持的 SQL 模式: {}",
                 s
@@ -46,8 +70,33 @@ impl SqlMode {
     }
 }
 
+/// 加载前清空目标表的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearTable {
+    /// TRUNCATE TABLE（SQLite 不支持，使用 DELETE FROM 代替）
+    Truncate,
+    /// DELETE FROM
+    Delete,
+}
+
+impl ClearTable {
+    /// 从字符串解析清空方式
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "truncate" => Ok(ClearTable::Truncate),
+            "delete" => Ok(ClearTable::Delete),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的清空表方式: {}",
+                s
+            ))),
+        }
+    }
+}
+
 impl SqlDialect {
     /// 从字符串解析方言
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "mysql" | "mariadb" => Ok(SqlDialect::MySQL),
@@ -55,6 +104,10 @@ impl SqlDialect {
             "sqlite" | "sqlite3" => Ok(SqlDialect::SQLite),
             "sqlserver" | "mssql" | "tsql" => Ok(SqlDialect::SqlServer),
             "oracle" => Ok(SqlDialect::Oracle),
+            "clickhouse" | "ch" => Ok(SqlDialect::ClickHouse),
+            "duckdb" => Ok(SqlDialect::DuckDB),
+            "snowflake" => Ok(SqlDialect::Snowflake),
+            "bigquery" | "bq" => Ok(SqlDialect::BigQuery),
             _ => Err(ExcelCliError::UnsupportedFormat(format!(
                 "不支持的 SQL 方言: {}",
                 s
@@ -62,22 +115,174 @@ impl SqlDialect {
         }
     }
 
-    /// 获取标识符引用符号
+    /// 引用标识符，并转义其中的引用字符（标识符不能包含 NUL，见 `check_identifier`）
     pub fn quote_identifier(&self, identifier: &str) -> String {
         match self {
-            SqlDialect::MySQL => format!("`{}`", identifier),
-            SqlDialect::PostgreSQL => format!("\"{}\"", identifier),
-            SqlDialect::SQLite => format!("\"{}\"", identifier),
-            SqlDialect::SqlServer => format!("[{}]", identifier),
-            SqlDialect::Oracle => format!("\"{}\"", identifier.to_uppercase()),
+            SqlDialect::MySQL => format!("`{}`", identifier.replace('`', "``")),
+            SqlDialect::PostgreSQL
+            | SqlDialect::SQLite
+            | SqlDialect::DuckDB
+            | SqlDialect::Snowflake => format!("\"{}\"", identifier.replace('"', "\"\"")),
+            SqlDialect::SqlServer => format!("[{}]", identifier.replace(']', "]]")),
+            // Oracle 的引用标识符不能包含双引号
+            SqlDialect::Oracle => format!("\"{}\"", identifier.to_uppercase().replace('"', "_")),
+            SqlDialect::ClickHouse | SqlDialect::BigQuery => {
+                format!("`{}`", identifier.replace('\\', "\\\\").replace('`', "\\`"))
+            }
+        }
+    }
+
+    /// 引用表名，支持 `db.schema.table` 形式的限定名（按 `.` 分段分别引用）
+    pub fn quote_table_name(&self, name: &str) -> String {
+        name.split('.')
+            .map(|part| self.quote_identifier(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// 第 `index` 个（从 1 开始）参数占位符
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            SqlDialect::PostgreSQL => format!("${}", index),
+            SqlDialect::SqlServer => format!("@p{}", index),
+            SqlDialect::Oracle => format!(":{}", index),
+            SqlDialect::MySQL
+            | SqlDialect::SQLite
+            | SqlDialect::ClickHouse
+            | SqlDialect::DuckDB
+            | SqlDialect::Snowflake
+            | SqlDialect::BigQuery => "?".to_string(),
+        }
+    }
+
+    /// 开始事务的语句（Oracle 隐式开始事务，返回 None）
+    fn begin_transaction(&self) -> Option<&'static str> {
+        match self {
+            SqlDialect::MySQL => Some("START TRANSACTION;"),
+            SqlDialect::PostgreSQL => Some("BEGIN;"),
+            SqlDialect::SQLite
+            | SqlDialect::SqlServer
+            | SqlDialect::DuckDB
+            | SqlDialect::Snowflake
+            | SqlDialect::BigQuery => Some("BEGIN TRANSACTION;"),
+            SqlDialect::Oracle | SqlDialect::ClickHouse => None,
+        }
+    }
+
+    /// 出错即停止执行的前置语句（对应各数据库的命令行客户端）
+    fn stop_on_error_preamble(&self) -> Option<&'static str> {
+        match self {
+            // mysql、clickhouse-client 和 BigQuery 脚本默认遇错即停止
+            SqlDialect::MySQL | SqlDialect::ClickHouse | SqlDialect::BigQuery => None,
+            SqlDialect::PostgreSQL => Some("\\set ON_ERROR_STOP on"),
+            SqlDialect::SQLite | SqlDialect::DuckDB => Some(".bail on"),
+            SqlDialect::Snowflake => Some("!set exit_on_error=true"),
+            SqlDialect::SqlServer => Some("SET XACT_ABORT ON;"),
+            SqlDialect::Oracle => Some("WHENEVER SQLERROR EXIT SQL.SQLCODE ROLLBACK"),
+        }
+    }
+
+    /// 是否支持多行 `VALUES` 的 INSERT 语句
+    fn supports_multi_row_insert(&self) -> bool {
+        !matches!(self, SqlDialect::SqlServer | SqlDialect::Oracle)
+    }
+
+    /// 字符串字面量能否通过转义表示 NUL 字符
+    pub(crate) fn supports_nul(&self) -> bool {
+        matches!(
+            self,
+            SqlDialect::MySQL
+                | SqlDialect::ClickHouse
+                | SqlDialect::BigQuery
+                | SqlDialect::Snowflake
+        )
+    }
+
+    /// UPDATE 语句开头（ClickHouse 使用 `ALTER TABLE ... UPDATE` 变更）
+    fn update_prefix(&self, quoted_table: &str) -> String {
+        match self {
+            SqlDialect::ClickHouse => format!("ALTER TABLE {} UPDATE", quoted_table),
+            _ => format!("UPDATE {} SET", quoted_table),
         }
     }
 
-    /// 获取字符串值引用符号
-    fn quote_string(&self, value: &str) -> String {
-        // 转义单引号
-        let escaped = value.replace('\'', "''");
-        format!("'{}'", escaped)
+    /// 生成字符串字面量，按方言转义特殊字符
+    pub(crate) fn quote_string(&self, value: &str) -> String {
+        match self {
+            // MySQL 默认把反斜杠作为转义字符
+            SqlDialect::MySQL => {
+                let mut out = String::with_capacity(value.len() + 2);
+                out.push('\'');
+                for c in value.chars() {
+                    match c {
+                        '\'' => out.push_str("''"),
+                        '\\' => out.push_str("\\\\"),
+                        '\0' => out.push_str("\\0"),
+                        '\u{1A}' => out.push_str("\\Z"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('\'');
+                out
+            }
+            // ClickHouse、BigQuery 和 Snowflake 使用反斜杠转义（BigQuery 的字面量不能包含换行）
+            SqlDialect::ClickHouse | SqlDialect::BigQuery | SqlDialect::Snowflake => {
+                let mut out = String::with_capacity(value.len() + 2);
+                out.push('\'');
+                for c in value.chars() {
+                    match c {
+                        '\'' => out.push_str("\\'"),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        '\0' if *self == SqlDialect::BigQuery => out.push_str("\\x00"),
+                        '\0' => out.push_str("\\0"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('\'');
+                out
+            }
+            // PostgreSQL：含控制字符时使用 E'' 转义字符串，否则使用标准字符串
+            SqlDialect::PostgreSQL => {
+                if value.chars().any(|c| c.is_control()) {
+                    let mut out = String::with_capacity(value.len() + 3);
+                    out.push_str("E'");
+                    for c in value.chars() {
+                        match c {
+                            '\'' => out.push_str("''"),
+                            '\\' => out.push_str("\\\\"),
+                            '\n' => out.push_str("\\n"),
+                            '\r' => out.push_str("\\r"),
+                            '\t' => out.push_str("\\t"),
+                            c if c.is_control() => {
+                                out.push_str(&format!("\\u{:04X}", c as u32));
+                            }
+                            c => out.push(c),
+                        }
+                    }
+                    out.push('\'');
+                    out
+                } else {
+                    format!("'{}'", value.replace('\'', "''"))
+                }
+            }
+            // SQL Server：含非 ASCII 字符时使用 N'' Unicode 字面量
+            SqlDialect::SqlServer => {
+                let escaped = value.replace('\'', "''");
+                if escaped.is_ascii() {
+                    format!("'{}'", escaped)
+                } else {
+                    format!("N'{}'", escaped)
+                }
+            }
+            SqlDialect::SQLite | SqlDialect::Oracle | SqlDialect::DuckDB => {
+                // 转义单引号（这些方言的字面量不能包含 NUL，见 `supports_nul`）
+                let escaped = value.replace('\'', "''");
+                format!("'{}'", escaped)
+            }
+        }
     }
 
     /// 格式化值
@@ -100,7 +305,11 @@ impl SqlDialect {
                         "0".to_string()
                     }
                 }
-                SqlDialect::PostgreSQL => {
+                SqlDialect::PostgreSQL
+                | SqlDialect::ClickHouse
+                | SqlDialect::DuckDB
+                | SqlDialect::Snowflake
+                | SqlDialect::BigQuery => {
                     if *b {
                         "TRUE".to_string()
                     } else {
@@ -118,9 +327,165 @@ impl SqlDialect {
             CellValue::Empty => "NULL".to_string(),
         }
     }
+
+    /// 格式化批量加载数据文件中的值（制表符分隔的文本格式，空值为 `\N`）
+    fn format_copy_value(&self, value: &CellValue) -> String {
+        match value {
+            CellValue::String(s) => escape_copy_text(s),
+            CellValue::Number(_) => self.format_value(value),
+            CellValue::Boolean(b) => match self {
+                SqlDialect::PostgreSQL => if *b { "t" } else { "f" }.to_string(),
+                _ => if *b { "1" } else { "0" }.to_string(),
+            },
+            CellValue::Empty => "\\N".to_string(),
+        }
+    }
+}
+
+/// 转义 COPY / LOAD DATA 文本格式中的特殊字符
+fn escape_copy_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 获取与输出编码对应的 MySQL 字符集名称
+fn mysql_charset(encoding: OutputEncoding) -> Result<&'static str> {
+    match encoding {
+        OutputEncoding::Utf8 => Ok("utf8mb4"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::GBK => Ok("gbk"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::GB18030 => Ok("gb18030"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::SHIFT_JIS => Ok("cp932"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::EUC_JP => Ok("ujis"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::BIG5 => Ok("big5"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::EUC_KR => Ok("euckr"),
+        OutputEncoding::Legacy(e) if e == encoding_rs::WINDOWS_1252 => Ok("latin1"),
+        _ => Err(ExcelCliError::ExportError(format!(
+            "MySQL LOAD DATA 不支持 {} 编码的数据文件",
+            encoding.name()
+        ))),
+    }
+}
+
+/// SQL 语句写入器，按语句计数并在需要时分段提交事务
+struct SqlWriter<'a> {
+    out: &'a mut dyn Write,
+    begin: Option<&'static str>,
+    commit_every: Option<usize>,
+    count: usize,
+}
+
+impl SqlWriter<'_> {
+    /// 写入一条语句，每满 `commit_every` 条语句提交一次
+    fn statement(&mut self, sql: &str) -> Result<()> {
+        self.statement_with(|out| Ok(out.write_all(sql.as_bytes())?))
+    }
+
+    /// 通过 `write` 分段写入一条语句（如内嵌数据的 COPY），提交规则与 `statement` 相同
+    fn statement_with<F>(&mut self, write: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn Write) -> Result<()>,
+    {
+        if let Some(n) = self.commit_every {
+            if self.count > 0 && self.count.is_multiple_of(n) {
+                writeln!(self.out, "COMMIT;")?;
+                if let Some(begin) = self.begin {
+                    writeln!(self.out, "{}", begin)?;
+                }
+            }
+        }
+        write(&mut *self.out)?;
+        writeln!(self.out)?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+impl Write for SqlWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// 读取同步模式使用的快照文件（`-f json` 导出的 JSON 文件）
+pub fn load_snapshot(path: &str) -> Result<Vec<ExcelRow>> {
+    let content = std::fs::read_to_string(path)?;
+    let rows: Vec<ExcelRow> = serde_json::from_str(content.trim_start_matches('\u{FEFF}'))?;
+    Ok(rows)
+}
+
+/// 预编译语句的参数值
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlParam {
+    /// 空值
+    Null,
+    /// 布尔值
+    Bool(bool),
+    /// 整数
+    Int(i64),
+    /// 浮点数
+    Float(f64),
+    /// 文本
+    Text(String),
+}
+
+impl From<&CellValue> for SqlParam {
+    fn from(value: &CellValue) -> Self {
+        match value {
+            CellValue::String(s) => SqlParam::Text(s.clone()),
+            // 与字面量输出一致，整数值使用整数类型
+            CellValue::Number(n)
+                if n.fract() == 0.0 && n.is_finite() && n.abs() < i64::MAX as f64 =>
+            {
+                SqlParam::Int(*n as i64)
+            }
+            CellValue::Number(n) => SqlParam::Float(*n),
+            CellValue::Boolean(b) => SqlParam::Bool(*b),
+            CellValue::Empty => SqlParam::Null,
+        }
+    }
+}
+
+/// 带占位符的 SQL 语句及其参数，参数顺序与占位符在语句中出现的顺序一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedStatement {
+    /// 含占位符的 SQL 语句
+    pub sql: String,
+    /// 按占位符顺序排列的参数
+    pub params: Vec<SqlParam>,
+}
+
+/// 检查标识符：任何方言的标识符都不能包含 NUL 字符
+pub(crate) fn check_identifier(kind: &str, name: &str) -> Result<()> {
+    if name.contains('\0') {
+        return Err(ExcelCliError::ExportError(format!(
+            "{} '{}' 包含 NUL 字符",
+            kind,
+            name.replace('\0', "\\0")
+        )));
+    }
+    Ok(())
+}
+
+/// 生成语句模板时代替值的标记，标识符已检查不含 NUL，不会与标记冲突
+fn param_marker(index: usize) -> String {
+    format!("\0{}\0", index)
 }
 
 /// SQL 导出器
+#[derive(Clone)]
 pub struct SqlExporter {
     dialect: SqlDialect,
     table_name: String,
@@ -129,6 +494,16 @@ pub struct SqlExporter {
     update_columns: Option<Vec<String>>,
     column_mapping: Option<Vec<String>>,
     batch_size: usize,
+    snapshot: Option<Vec<ExcelRow>>,
+    diff: Option<DataDiff>,
+    transaction: bool,
+    commit_every: Option<usize>,
+    stop_on_error: bool,
+    clear_table: Option<ClearTable>,
+    identity_insert: bool,
+    normalize_values: bool,
+    normalizer: Option<IdentifierNormalizer>,
+    encoder: TextEncoder,
 }
 
 impl SqlExporter {
@@ -142,6 +517,16 @@ impl SqlExporter {
             update_columns: None,
             column_mapping: None,
             batch_size: 1000,
+            snapshot: None,
+            diff: None,
+            transaction: false,
+            commit_every: None,
+            stop_on_error: false,
+            clear_table: None,
+            identity_insert: false,
+            normalize_values: false,
+            normalizer: None,
+            encoder: TextEncoder::default(),
         }
     }
 
@@ -175,6 +560,143 @@ impl SqlExporter {
         self
     }
 
+    /// 设置上次同步时的快照（用于 SYNC 模式，未设置时视为空表）
+    pub fn with_snapshot(mut self, rows: Vec<ExcelRow>) -> Self {
+        self.snapshot = Some(rows);
+        self
+    }
+
+    /// 设置已经算好的对比结果（用于 SYNC 模式，代替快照），生成的语句与 `diff` 命令的其他输出一致
+    pub fn with_diff(mut self, diff: DataDiff) -> Self {
+        self.diff = Some(diff);
+        self
+    }
+
+    /// 设置是否用事务包裹所有语句
+    pub fn with_transaction(mut self, transaction: bool) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// 设置每 N 条语句提交一次（同时启用事务）
+    pub fn with_commit_every(mut self, statements: usize) -> Self {
+        self.commit_every = Some(statements.max(1));
+        self.transaction = true;
+        self
+    }
+
+    /// 设置是否在开头写入出错即停止的前置语句
+    pub fn with_stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+
+    /// 设置加载前清空目标表的方式
+    pub fn with_clear_table(mut self, clear: ClearTable) -> Self {
+        self.clear_table = Some(clear);
+        self
+    }
+
+    /// 设置是否允许写入标识列（仅 SQL Server，生成 SET IDENTITY_INSERT）
+    pub fn with_identity_insert(mut self, identity_insert: bool) -> Self {
+        self.identity_insert = identity_insert;
+        self
+    }
+
+    /// 设置是否按推断的列类型转换布尔、日期文本（与 schema 生成的 DDL 一致）
+    pub fn with_normalize_values(mut self, normalize_values: bool) -> Self {
+        self.normalize_values = normalize_values;
+        self
+    }
+
+    /// 设置标识符规范化（列名、表名的风格、保留字、长度限制和重命名）
+    pub fn with_normalizer(mut self, normalizer: IdentifierNormalizer) -> Self {
+        self.normalizer = Some(normalizer);
+        self
+    }
+
+    /// 设置输出编码
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// 应用标识符规范化，返回使用规范化后表名和列名的导出器
+    ///
+    /// 显式的列名映射优先于规范化结果；主键列和更新列可以使用原列名或规范化后的列名
+    fn normalized(&self, normalizer: &IdentifierNormalizer, data: &ExcelData) -> Result<Self> {
+        let mut exporter = self.clone();
+        exporter.normalizer = None;
+        exporter.table_name = normalizer.normalize_table(&self.table_name);
+
+        let names = match &self.column_mapping {
+            Some(mapping) => mapping.clone(),
+            None => normalizer.normalize_all(&data.headers)?,
+        };
+        let rename = |column: &String| {
+            data.headers
+                .iter()
+                .position(|h| h == column)
+                .and_then(|i| names.get(i))
+                .unwrap_or(column)
+                .clone()
+        };
+        exporter.primary_keys = self.primary_keys.iter().map(rename).collect();
+        exporter.update_columns = self
+            .update_columns
+            .as_ref()
+            .map(|cols| cols.iter().map(rename).collect());
+        exporter.column_mapping = Some(names);
+        Ok(exporter)
+    }
+
+    /// 检查表名、列名和字符串值中的 NUL 字符，避免生成的 SQL 静默丢失字符
+    fn check_nul(&self, data: &ExcelData) -> Result<()> {
+        check_identifier("表名", &self.table_name)?;
+        for column in self.get_column_names(data) {
+            check_identifier("列名", &column)?;
+        }
+        if self.dialect.supports_nul() {
+            return Ok(());
+        }
+        for (i, row) in data.rows.iter().enumerate() {
+            for header in &data.headers {
+                if matches!(row.data.get(header), Some(CellValue::String(s)) if s.contains('\0')) {
+                    return Err(ExcelCliError::ExportError(format!(
+                        "第 {} 行 '{}' 列的值包含 NUL 字符，{:?} 的字符串无法表示",
+                        i + 1,
+                        header,
+                        self.dialect
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 验证加载选项与方言、模式是否兼容
+    fn validate_load_options(&self) -> Result<()> {
+        if self.identity_insert && self.dialect != SqlDialect::SqlServer {
+            return Err(ExcelCliError::ExportError(
+                "IDENTITY_INSERT 仅适用于 SQL Server".to_string(),
+            ));
+        }
+        if self.transaction && self.dialect == SqlDialect::ClickHouse {
+            return Err(ExcelCliError::ExportError(
+                "ClickHouse 不支持事务，不能使用 --transaction 或 --commit-every".to_string(),
+            ));
+        }
+        if self.clear_table.is_some()
+            && matches!(self.mode, SqlMode::Update | SqlMode::Delete | SqlMode::Sync)
+        {
+            return Err(ExcelCliError::ExportError(format!(
+                "{:?} 模式不能在加载前清空表",
+                self.mode
+            )));
+        }
+        Ok(())
+    }
+
     /// 验证列名映射
     fn validate_column_mapping(&self, data: &ExcelData) -> Result<()> {
         if let Some(mapping) = &self.column_mapping {
@@ -212,7 +734,7 @@ impl SqlExporter {
 
         format!(
             "INSERT INTO {} ({}) VALUES ({});",
-            self.dialect.quote_identifier(table),
+            self.dialect.quote_table_name(table),
             quoted_columns.join(", "),
             row_values.join(", ")
         )
@@ -237,7 +759,7 @@ impl SqlExporter {
 
         format!(
             "INSERT INTO {} ({}) VALUES\n{};",
-            self.dialect.quote_identifier(table),
+            self.dialect.quote_table_name(table),
             quoted_columns.join(", "),
             values_clauses.join(",\n")
         )
@@ -303,19 +825,20 @@ impl SqlExporter {
             .collect();
 
         Ok(format!(
-            "UPDATE {} SET {} WHERE {};",
-            self.dialect.quote_identifier(table),
+            "{} {} WHERE {};",
+            self.dialect
+                .update_prefix(&self.dialect.quote_table_name(table)),
             set_clauses.join(", "),
             where_clauses.join(" AND ")
         ))
     }
 
-    /// 生成 UPSERT 语句（根据方言不同使用不同语法）
+    /// 生成 UPSERT 语句（根据方言不同使用不同语法），多行合并为一条语句
     fn generate_upsert(
         &self,
         table: &str,
         columns: &[String],
-        row_values: &[String],
+        rows: &[Vec<String>],
     ) -> Result<String> {
         if self.primary_keys.is_empty() {
             return Err(ExcelCliError::ExportError(
@@ -327,184 +850,543 @@ impl SqlExporter {
             .iter()
             .map(|col| self.dialect.quote_identifier(col))
             .collect();
+        let quoted_table = self.dialect.quote_table_name(table);
+        let non_key_columns: Vec<String> = columns
+            .iter()
+            .filter(|col| !self.primary_keys.contains(col))
+            .map(|col| self.dialect.quote_identifier(col))
+            .collect();
 
-        let quoted_table = self.dialect.quote_identifier(table);
+        let values_clause = match rows {
+            [row] => format!("VALUES ({})", row.join(", ")),
+            _ => {
+                let tuples: Vec<String> = rows
+                    .iter()
+                    .map(|row| format!("({})", row.join(", ")))
+                    .collect();
+                format!("VALUES\n{}", tuples.join(",\n"))
+            }
+        };
 
         match self.dialect {
             SqlDialect::MySQL => {
                 // MySQL: INSERT ... ON DUPLICATE KEY UPDATE
-                let update_clauses: Vec<String> = columns
-                    .iter()
-                    .filter(|col| !self.primary_keys.contains(col))
-                    .map(|col| {
-                        let quoted = self.dialect.quote_identifier(col);
-                        format!("{} = VALUES({})", quoted, quoted)
-                    })
-                    .collect();
+                // 所有列都是主键时没有可更新的列，用主键自赋值实现“存在则跳过”
+                let update_clauses: Vec<String> = if non_key_columns.is_empty() {
+                    let quoted = self.dialect.quote_identifier(&self.primary_keys[0]);
+                    vec![format!("{} = {}", quoted, quoted)]
+                } else {
+                    non_key_columns
+                        .iter()
+                        .map(|quoted| format!("{} = VALUES({})", quoted, quoted))
+                        .collect()
+                };
 
                 Ok(format!(
-                    "INSERT INTO {} ({}) VALUES ({}) ON DUPLICATE KEY UPDATE {};",
+                    "INSERT INTO {} ({}) {} ON DUPLICATE KEY UPDATE {};",
                     quoted_table,
                     quoted_columns.join(", "),
-                    row_values.join(", "),
+                    values_clause,
                     update_clauses.join(", ")
                 ))
             }
-            SqlDialect::PostgreSQL => {
-                // PostgreSQL: INSERT ... ON CONFLICT ... DO UPDATE
+            SqlDialect::PostgreSQL | SqlDialect::SQLite => {
+                // PostgreSQL/SQLite: INSERT ... ON CONFLICT ... DO UPDATE
+                let excluded = if self.dialect == SqlDialect::PostgreSQL {
+                    "EXCLUDED"
+                } else {
+                    "excluded"
+                };
                 let conflict_columns: Vec<String> = self
                     .primary_keys
                     .iter()
                     .map(|pk| self.dialect.quote_identifier(pk))
                     .collect();
 
-                let update_clauses: Vec<String> = columns
-                    .iter()
-                    .filter(|col| !self.primary_keys.contains(col))
-                    .map(|col| {
-                        let quoted = self.dialect.quote_identifier(col);
-                        format!("{} = EXCLUDED.{}", quoted, quoted)
-                    })
-                    .collect();
+                let action = if non_key_columns.is_empty() {
+                    "DO NOTHING".to_string()
+                } else {
+                    let update_clauses: Vec<String> = non_key_columns
+                        .iter()
+                        .map(|quoted| format!("{} = {}.{}", quoted, excluded, quoted))
+                        .collect();
+                    format!("DO UPDATE SET {}", update_clauses.join(", "))
+                };
 
                 Ok(format!(
-                    "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) DO UPDATE SET {};",
+                    "INSERT INTO {} ({}) {} ON CONFLICT ({}) {};",
                     quoted_table,
                     quoted_columns.join(", "),
-                    row_values.join(", "),
+                    values_clause,
                     conflict_columns.join(", "),
-                    update_clauses.join(", ")
+                    action
                 ))
             }
-            SqlDialect::SQLite => {
-                // SQLite: INSERT ... ON CONFLICT ... DO UPDATE
-                let conflict_columns: Vec<String> = self
-                    .primary_keys
-                    .iter()
-                    .map(|pk| self.dialect.quote_identifier(pk))
-                    .collect();
-
-                let update_clauses: Vec<String> = columns
-                    .iter()
-                    .filter(|col| !self.primary_keys.contains(col))
-                    .map(|col| {
-                        let quoted = self.dialect.quote_identifier(col);
-                        format!("{} = excluded.{}", quoted, quoted)
-                    })
-                    .collect();
-
+            SqlDialect::DuckDB => {
+                // DuckDB: INSERT OR REPLACE（所有列都是主键时 INSERT OR IGNORE）
+                let verb = if non_key_columns.is_empty() {
+                    "INSERT OR IGNORE"
+                } else {
+                    "INSERT OR REPLACE"
+                };
                 Ok(format!(
-                    "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) DO UPDATE SET {};",
+                    "{} INTO {} ({}) {};",
+                    verb,
                     quoted_table,
                     quoted_columns.join(", "),
-                    row_values.join(", "),
-                    conflict_columns.join(", "),
-                    update_clauses.join(", ")
+                    values_clause
                 ))
             }
-            SqlDialect::SqlServer | SqlDialect::Oracle => {
-                // SQL Server/Oracle: MERGE statement
-                let source_values: Vec<String> = columns
-                    .iter()
-                    .zip(row_values.iter())
-                    .map(|(col, val)| format!("{} AS {}", val, self.dialect.quote_identifier(col)))
-                    .collect();
-
-                let match_conditions: Vec<String> = self
-                    .primary_keys
-                    .iter()
-                    .map(|pk| {
-                        let quoted = self.dialect.quote_identifier(pk);
-                        format!("target.{} = source.{}", quoted, quoted)
-                    })
-                    .collect();
-
-                let update_set: Vec<String> = columns
-                    .iter()
-                    .filter(|col| !self.primary_keys.contains(col))
-                    .map(|col| {
-                        let quoted = self.dialect.quote_identifier(col);
-                        format!("target.{} = source.{}", quoted, quoted)
-                    })
-                    .collect();
-
-                let insert_cols = quoted_columns.join(", ");
-                let insert_vals: Vec<String> = columns
-                    .iter()
-                    .map(|col| format!("source.{}", self.dialect.quote_identifier(col)))
-                    .collect();
-
+            SqlDialect::ClickHouse => {
+                // ClickHouse: 普通 INSERT，由 ReplacingMergeTree 按排序键保留最新版本
                 Ok(format!(
-                    "MERGE INTO {} AS target USING (SELECT {}) AS source ON ({}) WHEN MATCHED THEN UPDATE SET {} WHEN NOT MATCHED THEN INSERT ({}) VALUES ({});",
+                    "INSERT INTO {} ({}) {};",
                     quoted_table,
-                    source_values.join(", "),
-                    match_conditions.join(" AND "),
-                    update_set.join(", "),
-                    insert_cols,
-                    insert_vals.join(", ")
+                    quoted_columns.join(", "),
+                    values_clause
                 ))
             }
+            SqlDialect::SqlServer => {
+                Ok(self.generate_merge_sqlserver(&quoted_table, &quoted_columns, rows))
+            }
+            SqlDialect::Oracle => {
+                Ok(self.generate_merge_oracle(&quoted_table, &quoted_columns, rows))
+            }
+            SqlDialect::Snowflake => {
+                Ok(self.generate_merge_snowflake(&quoted_table, &quoted_columns, rows))
+            }
+            SqlDialect::BigQuery => {
+                Ok(self.generate_merge_bigquery(&quoted_table, &quoted_columns, rows))
+            }
         }
     }
-}
-
-impl Exporter for SqlExporter {
-    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
-        // 验证列名映射
-        self.validate_column_mapping(data)?;
 
-        let mut file = File::create(output_path)?;
+    /// 生成 MERGE 语句的 ON、WHEN MATCHED 和 WHEN NOT MATCHED 部分
+    ///
+    /// 所有列都是主键时省略 WHEN MATCHED（空的 UPDATE SET 在任何方言中都无效）
+    fn merge_clauses(&self, quoted_columns: &[String], qualify_target: bool) -> String {
+        let target = if qualify_target { "target." } else { "" };
+        let match_conditions: Vec<String> = self
+            .primary_keys
+            .iter()
+            .map(|pk| {
+                let quoted = self.dialect.quote_identifier(pk);
+                format!("target.{} = source.{}", quoted, quoted)
+            })
+            .collect();
 
-        // 获取列名
-        let column_names = self.get_column_names(data);
+        let key_columns: Vec<String> = self
+            .primary_keys
+            .iter()
+            .map(|pk| self.dialect.quote_identifier(pk))
+            .collect();
+        let update_set: Vec<String> = quoted_columns
+            .iter()
+            .filter(|col| !key_columns.contains(col))
+            .map(|col| format!("{}{} = source.{}", target, col, col))
+            .collect();
 
-        // 写入文件头注释
-        writeln!(file, "-- Generated by excel-cli")?;
-        writeln!(file, "-- Dialect: {:?}", self.dialect)?;
-        writeln!(file, "-- Mode: {:?}", self.mode)?;
-        writeln!(file, "-- Table: {}", self.table_name)?;
-        writeln!(file, "-- Rows: {}", data.row_count())?;
-        writeln!(file)?;
+        let insert_vals: Vec<String> = quoted_columns
+            .iter()
+            .map(|col| format!("source.{}", col))
+            .collect();
 
-        // 根据模式导出
-        match self.mode {
-            SqlMode::Insert => {
-                self.export_insert(data, &mut file, &column_names)?;
-            }
-            SqlMode::Update => {
-                self.export_update(data, &mut file, &column_names)?;
-            }
-            SqlMode::Upsert => {
-                self.export_upsert(data, &mut file, &column_names)?;
-            }
+        let mut clauses = format!("ON ({})", match_conditions.join(" AND "));
+        if !update_set.is_empty() {
+            clauses.push_str(&format!(
+                "\nWHEN MATCHED THEN UPDATE SET {}",
+                update_set.join(", ")
+            ));
         }
-
-        file.flush()?;
-        Ok(())
+        clauses.push_str(&format!(
+            "\nWHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+            quoted_columns.join(", "),
+            insert_vals.join(", ")
+        ));
+        clauses
     }
 
-    fn format_name(&self) -> &'static str {
-        "SQL"
-    }
+    /// 生成 SQL Server MERGE 语句
+    ///
+    /// 使用 `WITH (HOLDLOCK)` 避免并发插入时的竞争，MERGE 必须以分号结尾
+    fn generate_merge_sqlserver(
+        &self,
+        quoted_table: &str,
+        quoted_columns: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let tuples: Vec<String> = rows
+            .iter()
+            .map(|row| format!("({})", row.join(", ")))
+            .collect();
 
-    fn file_extension(&self) -> &'static str {
-        "sql"
+        format!(
+            "MERGE INTO {} WITH (HOLDLOCK) AS target\nUSING (VALUES {}) AS source ({})\n{};",
+            quoted_table,
+            tuples.join(", "),
+            quoted_columns.join(", "),
+            self.merge_clauses(quoted_columns, true)
+        )
+    }
+
+    /// 生成 Oracle MERGE 语句
+    ///
+    /// Oracle 的表别名不能使用 `AS`，且不支持 VALUES 表构造器，数据源使用 `SELECT ... FROM DUAL UNION ALL`
+    fn generate_merge_oracle(
+        &self,
+        quoted_table: &str,
+        quoted_columns: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let selects: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let source_values: Vec<String> = row
+                    .iter()
+                    .zip(quoted_columns)
+                    .map(|(val, col)| format!("{} AS {}", val, col))
+                    .collect();
+                format!("SELECT {} FROM DUAL", source_values.join(", "))
+            })
+            .collect();
+
+        format!(
+            "MERGE INTO {} target\nUSING ({}) source\n{};",
+            quoted_table,
+            selects.join(" UNION ALL "),
+            self.merge_clauses(quoted_columns, true)
+        )
+    }
+
+    /// 生成 Snowflake MERGE 语句，数据源使用 VALUES 子查询
+    fn generate_merge_snowflake(
+        &self,
+        quoted_table: &str,
+        quoted_columns: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let tuples: Vec<String> = rows
+            .iter()
+            .map(|row| format!("({})", row.join(", ")))
+            .collect();
+
+        format!(
+            "MERGE INTO {} AS target\nUSING (SELECT * FROM (VALUES {}) AS v ({})) AS source\n{};",
+            quoted_table,
+            tuples.join(", "),
+            quoted_columns.join(", "),
+            self.merge_clauses(quoted_columns, true)
+        )
+    }
+
+    /// 生成 BigQuery MERGE 语句
+    ///
+    /// 数据源使用 `SELECT ... UNION ALL`，UPDATE SET 中的目标列不能带表别名
+    fn generate_merge_bigquery(
+        &self,
+        quoted_table: &str,
+        quoted_columns: &[String],
+        rows: &[Vec<String>],
+    ) -> String {
+        let selects: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let source_values: Vec<String> = row
+                    .iter()
+                    .zip(quoted_columns)
+                    .map(|(val, col)| format!("{} AS {}", val, col))
+                    .collect();
+                format!("SELECT {}", source_values.join(", "))
+            })
+            .collect();
+
+        format!(
+            "MERGE INTO {} AS target\nUSING ({}) AS source\n{};",
+            quoted_table,
+            selects.join(" UNION ALL "),
+            self.merge_clauses(quoted_columns, false)
+        )
+    }
+
+    /// 生成多行合并的 UPDATE 语句，每列用 `CASE` 表达式按主键选择新值
+    ///
+    /// `CASE` 的结果类型由 `ELSE` 中的目标列决定，PostgreSQL 会把字符串字面量和 NULL
+    /// 转换为列的类型（DATE、UUID、JSON 等）；`UPDATE ... FROM (VALUES ...)` 则会把它们当作 text
+    fn generate_batch_update(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+    ) -> Result<String> {
+        let key_indices = self.primary_key_indices(columns)?;
+        let update_cols: Vec<(usize, &String)> = columns
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| !self.primary_keys.contains(col))
+            .filter(|(_, col)| {
+                self.update_columns
+                    .as_ref()
+                    .is_none_or(|only| only.contains(col))
+            })
+            .collect();
+
+        if update_cols.is_empty() {
+            return Err(ExcelCliError::ExportError(
+                "没有可更新的列（所有列都是主键）".to_string(),
+            ));
+        }
+
+        let quoted_table = self.dialect.quote_table_name(table);
+        let keys: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| key_indices.iter().map(|&i| row[i].clone()).collect())
+            .collect();
+
+        let set_clauses: Vec<String> = update_cols
+            .iter()
+            .map(|(idx, col)| {
+                let quoted = self.dialect.quote_identifier(col);
+                let whens: Vec<String> = rows
+                    .iter()
+                    .zip(&keys)
+                    .map(|(row, key)| {
+                        format!(
+                            "WHEN {} THEN {}",
+                            self.keys_condition(std::slice::from_ref(key)),
+                            row[*idx]
+                        )
+                    })
+                    .collect();
+                format!(
+                    "    {} = CASE {} ELSE {} END",
+                    quoted,
+                    whens.join(" "),
+                    quoted
+                )
+            })
+            .collect();
+
+        Ok(format!(
+            "{}\n{}\nWHERE {};",
+            self.dialect.update_prefix(&quoted_table),
+            set_clauses.join(",\n"),
+            self.keys_condition(&keys)
+        ))
+    }
+
+    /// 生成带占位符的语句，每行数据一条，供调用方用自己的数据库驱动绑定参数执行
+    ///
+    /// 同一模式下所有语句的 SQL 文本相同，只需预编译一次。支持 INSERT、UPDATE、UPSERT 和
+    /// DELETE 模式；不受批量大小和事务选项影响
+    pub fn prepared_statements(&self, data: &ExcelData) -> Result<Vec<PreparedStatement>> {
+        self.validate_column_mapping(data)?;
+
+        if let Some(normalizer) = &self.normalizer {
+            return self.normalized(normalizer, data)?.prepared_statements(data);
+        }
+
+        // 参数值单独传递，只需检查标识符
+        check_identifier("表名", &self.table_name)?;
+        let column_names = self.get_column_names(data);
+        for column in &column_names {
+            check_identifier("列名", column)?;
+        }
+        let markers: Vec<String> = (0..column_names.len()).map(param_marker).collect();
+
+        let template = match self.mode {
+            SqlMode::Insert => {
+                self.generate_single_insert(&self.table_name, &column_names, &markers)
+            }
+            SqlMode::Update => {
+                self.generate_update(&self.table_name, &column_names, &markers, data)?
+            }
+            SqlMode::Upsert => self.generate_upsert(
+                &self.table_name,
+                &column_names,
+                std::slice::from_ref(&markers),
+            )?,
+            SqlMode::Delete => {
+                let key = self
+                    .primary_key_indices(&column_names)?
+                    .into_iter()
+                    .map(|i| markers[i].clone())
+                    .collect();
+                format!(
+                    "DELETE FROM {} WHERE {};",
+                    self.dialect.quote_table_name(&self.table_name),
+                    self.keys_condition(&[key])
+                )
+            }
+            SqlMode::Copy | SqlMode::Sync => {
+                return Err(ExcelCliError::ExportError(format!(
+                    "{:?} 模式不支持生成预编译语句",
+                    self.mode
+                )));
+            }
+        };
+
+        // 把标记替换为方言的占位符，并记录每个占位符对应的列
+        let mut sql = String::with_capacity(template.len());
+        let mut order = Vec::new();
+        for (i, part) in template.split('\0').enumerate() {
+            if i.is_multiple_of(2) {
+                sql.push_str(part);
+            } else {
+                order.push(part.parse::<usize>().expect("参数标记为列序号"));
+                sql.push_str(&self.dialect.placeholder(order.len()));
+            }
+        }
+
+        let data = self.normalize_values(data);
+        Ok(data
+            .rows
+            .iter()
+            .map(|row| PreparedStatement {
+                sql: sql.clone(),
+                params: order
+                    .iter()
+                    .map(|&i| {
+                        SqlParam::from(row.data.get(&data.headers[i]).unwrap_or(&CellValue::Empty))
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+impl Exporter for SqlExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        // 验证列名映射
+        self.validate_column_mapping(data)?;
+
+        if let Some(normalizer) = &self.normalizer {
+            return self.normalized(normalizer, data)?.export(data, output_path);
+        }
+
+        self.validate_load_options()?;
+        self.check_nul(data)?;
+
+        let data = self.normalize_values(data);
+        let data = data.as_ref();
+
+        // 边生成边转换编码写入文件
+        self.encoder
+            .write_with(output_path, |out| self.write_sql(data, out, output_path))
+    }
+
+    fn format_name(&self) -> &'static str {
+        "SQL"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "sql"
     }
 }
 
 impl SqlExporter {
+    /// 启用 `normalize_values` 时，布尔、日期文本按推断的列类型转换
+    fn normalize_values<'d>(&self, data: &'d ExcelData) -> Cow<'d, ExcelData> {
+        if self.normalize_values {
+            TypeInference::normalize_values(data)
+        } else {
+            Cow::Borrowed(data)
+        }
+    }
+
+    /// 生成 SQL 文件内容并写入 `out`
+    fn write_sql(&self, data: &ExcelData, out: &mut dyn Write, output_path: &str) -> Result<()> {
+        let begin = if self.transaction {
+            self.dialect.begin_transaction()
+        } else {
+            None
+        };
+        let mut file = SqlWriter {
+            out,
+            begin,
+            commit_every: self.commit_every,
+            count: 0,
+        };
+
+        // 获取列名
+        let column_names = self.get_column_names(data);
+
+        // 写入文件头注释
+        writeln!(file, "-- Generated by excel-cli")?;
+        writeln!(file, "-- Dialect: {:?}", self.dialect)?;
+        writeln!(file, "-- Mode: {:?}", self.mode)?;
+        writeln!(file, "-- Table: {}", self.table_name)?;
+        writeln!(file, "-- Rows: {}", data.row_count())?;
+        writeln!(file)?;
+
+        // 前置语句：出错即停止、开始事务、清空表、允许写入标识列
+        let quoted_table = self.dialect.quote_table_name(&self.table_name);
+        if self.stop_on_error {
+            if let Some(preamble) = self.dialect.stop_on_error_preamble() {
+                writeln!(file, "{}", preamble)?;
+            }
+        }
+        if let Some(begin) = begin {
+            writeln!(file, "{}", begin)?;
+        }
+        match self.clear_table {
+            Some(ClearTable::Truncate) if self.dialect != SqlDialect::SQLite => {
+                writeln!(file, "TRUNCATE TABLE {};", quoted_table)?;
+            }
+            Some(_) => writeln!(file, "DELETE FROM {};", quoted_table)?,
+            None => {}
+        }
+        if self.identity_insert {
+            writeln!(file, "SET IDENTITY_INSERT {} ON;", quoted_table)?;
+        }
+        if self.stop_on_error
+            || self.transaction
+            || self.clear_table.is_some()
+            || self.identity_insert
+        {
+            writeln!(file)?;
+        }
+
+        // 根据模式导出
+        match self.mode {
+            SqlMode::Insert => {
+                self.export_insert(data, &mut file, &column_names)?;
+            }
+            SqlMode::Update => {
+                self.export_update(data, &mut file, &column_names)?;
+            }
+            SqlMode::Upsert => {
+                self.export_upsert(data, &mut file, &column_names)?;
+            }
+            SqlMode::Copy => {
+                self.export_copy(data, &mut file, &column_names, output_path)?;
+            }
+            SqlMode::Delete => {
+                self.export_delete(data, &mut file, &column_names)?;
+            }
+            SqlMode::Sync => {
+                self.export_sync(data, &mut file, &column_names)?;
+            }
+        }
+
+        if self.identity_insert {
+            writeln!(file, "SET IDENTITY_INSERT {} OFF;", quoted_table)?;
+        }
+        if self.transaction {
+            writeln!(file, "COMMIT;")?;
+        }
+
+        Ok(())
+    }
+
     /// 导出 INSERT 语句
     fn export_insert(
         &self,
         data: &ExcelData,
-        file: &mut File,
+        file: &mut SqlWriter,
         column_names: &[String],
     ) -> Result<()> {
         // 根据方言选择批量或单条插入
-        let use_batch = matches!(
-            self.dialect,
-            SqlDialect::MySQL | SqlDialect::PostgreSQL | SqlDialect::SQLite
-        ) && self.batch_size > 1;
+        let use_batch = self.dialect.supports_multi_row_insert() && self.batch_size > 1;
 
         if use_batch {
             // 批量插入
@@ -524,7 +1406,7 @@ impl SqlExporter {
                 if batch_values.len() >= self.batch_size || idx == data.rows.len() - 1 {
                     let sql =
                         self.generate_batch_insert(&self.table_name, column_names, &batch_values);
-                    writeln!(file, "{}", sql)?;
+                    file.statement(&sql)?;
                     writeln!(file)?;
                     batch_values.clear();
                 }
@@ -540,7 +1422,7 @@ impl SqlExporter {
                 }
 
                 let sql = self.generate_single_insert(&self.table_name, column_names, &row_values);
-                writeln!(file, "{}", sql)?;
+                file.statement(&sql)?;
             }
         }
 
@@ -551,41 +1433,395 @@ impl SqlExporter {
     fn export_update(
         &self,
         data: &ExcelData,
-        file: &mut File,
+        file: &mut SqlWriter,
         column_names: &[String],
     ) -> Result<()> {
-        for row in &data.rows {
-            let mut row_values = Vec::new();
-
-            for header in &data.headers {
-                let value = row.data.get(header).unwrap_or(&CellValue::Empty);
-                row_values.push(self.dialect.format_value(value));
-            }
+        let rows: Vec<Vec<String>> = data
+            .rows
+            .iter()
+            .map(|row| self.format_row(&data.headers, row))
+            .collect();
 
-            let sql = self.generate_update(&self.table_name, column_names, &row_values, data)?;
-            writeln!(file, "{}", sql)?;
+        for chunk in self.key_batches(&rows, column_names)? {
+            let sql = match chunk {
+                [row_values] => {
+                    self.generate_update(&self.table_name, column_names, row_values, data)?
+                }
+                _ => self.generate_batch_update(&self.table_name, column_names, chunk)?,
+            };
+            file.statement(&sql)?;
         }
 
         Ok(())
     }
 
+    /// 按批量大小分批，同一批中主键重复时另起一批
+    ///
+    /// ON CONFLICT 和 MERGE 不允许一条语句多次修改同一行，CASE 只取第一个匹配的值；
+    /// 分开执行才能与逐行生成时一样由后一行生效
+    fn key_batches<'r>(
+        &self,
+        rows: &'r [Vec<String>],
+        column_names: &[String],
+    ) -> Result<Vec<&'r [Vec<String>]>> {
+        let key_indices = self.primary_key_indices(column_names)?;
+        let batch_size = self.batch_size.max(1);
+        let mut batches = Vec::new();
+        let mut start = 0;
+        let mut keys = HashSet::new();
+        for (i, row) in rows.iter().enumerate() {
+            let key: Vec<&String> = key_indices.iter().map(|&k| &row[k]).collect();
+            if i - start == batch_size || !keys.insert(key.clone()) {
+                batches.push(&rows[start..i]);
+                start = i;
+                keys.clear();
+                keys.insert(key);
+            }
+        }
+        if start < rows.len() {
+            batches.push(&rows[start..]);
+        }
+        Ok(batches)
+    }
+
     /// 导出 UPSERT 语句
     fn export_upsert(
         &self,
         data: &ExcelData,
-        file: &mut File,
+        file: &mut SqlWriter,
         column_names: &[String],
     ) -> Result<()> {
-        for row in &data.rows {
-            let mut row_values = Vec::new();
+        let rows: Vec<Vec<String>> = data
+            .rows
+            .iter()
+            .map(|row| self.format_row(&data.headers, row))
+            .collect();
 
-            for header in &data.headers {
-                let value = row.data.get(header).unwrap_or(&CellValue::Empty);
-                row_values.push(self.dialect.format_value(value));
+        for chunk in self.key_batches(&rows, column_names)? {
+            let sql = self.generate_upsert(&self.table_name, column_names, chunk)?;
+            file.statement(&sql)?;
+        }
+
+        Ok(())
+    }
+
+    /// 导出批量加载语句
+    ///
+    /// PostgreSQL 将数据内嵌在 `COPY ... FROM stdin` 块中；MySQL 将数据写入输出文件旁的
+    /// `.data.tsv` 文件，并生成对应的 `LOAD DATA LOCAL INFILE` 语句
+    fn export_copy(
+        &self,
+        data: &ExcelData,
+        file: &mut SqlWriter,
+        column_names: &[String],
+        output_path: &str,
+    ) -> Result<()> {
+        let quoted_columns: Vec<String> = column_names
+            .iter()
+            .map(|col| self.dialect.quote_identifier(col))
+            .collect();
+        let quoted_table = self.dialect.quote_table_name(&self.table_name);
+
+        let write_rows = |out: &mut dyn Write| -> Result<()> {
+            for row in &data.rows {
+                let values: Vec<String> = data
+                    .headers
+                    .iter()
+                    .map(|h| {
+                        self.dialect
+                            .format_copy_value(row.data.get(h).unwrap_or(&CellValue::Empty))
+                    })
+                    .collect();
+                writeln!(out, "{}", values.join("\t"))?;
             }
+            Ok(())
+        };
 
-            let sql = self.generate_upsert(&self.table_name, column_names, &row_values)?;
-            writeln!(file, "{}", sql)?;
+        match self.dialect {
+            SqlDialect::PostgreSQL => {
+                file.statement_with(|out| {
+                    writeln!(
+                        out,
+                        "COPY {} ({}) FROM stdin;",
+                        quoted_table,
+                        quoted_columns.join(", ")
+                    )?;
+                    write_rows(out)?;
+                    write!(out, "\\.")?;
+                    Ok(())
+                })?;
+            }
+            SqlDialect::MySQL => {
+                let charset = mysql_charset(self.encoder.encoding())?;
+                let data_path = Path::new(output_path).with_extension("data.tsv");
+                let data_path = data_path.to_string_lossy();
+                self.encoder.write_with(&data_path, write_rows)?;
+
+                file.statement(&format!(
+                    "LOAD DATA LOCAL INFILE {} INTO TABLE {} CHARACTER SET {} FIELDS TERMINATED BY '\\t' ESCAPED BY '\\\\' LINES TERMINATED BY '\\n' ({});",
+                    self.dialect.quote_string(&data_path.replace('\\', "/")),
+                    quoted_table,
+                    charset,
+                    quoted_columns.join(", ")
+                ))?;
+            }
+            _ => {
+                return Err(ExcelCliError::ExportError(format!(
+                    "COPY 模式仅支持 PostgreSQL 和 MySQL，当前方言: {:?}",
+                    self.dialect
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 获取主键列在列名中的位置
+    fn primary_key_indices(&self, column_names: &[String]) -> Result<Vec<usize>> {
+        if self.primary_keys.is_empty() {
+            return Err(ExcelCliError::ExportError(format!(
+                "{:?} 模式需要指定主键列（--primary-keys）",
+                self.mode
+            )));
+        }
+
+        self.primary_keys
+            .iter()
+            .map(|pk| {
+                column_names
+                    .iter()
+                    .position(|c| c == pk)
+                    .ok_or_else(|| ExcelCliError::ExportError(format!("主键列 '{}' 不存在", pk)))
+            })
+            .collect()
+    }
+
+    /// 格式化一行的所有值
+    fn format_row(&self, headers: &[String], row: &ExcelRow) -> Vec<String> {
+        headers
+            .iter()
+            .map(|h| {
+                self.dialect
+                    .format_value(row.data.get(h).unwrap_or(&CellValue::Empty))
+            })
+            .collect()
+    }
+
+    /// 生成按主键匹配多行的 WHERE 条件
+    ///
+    /// 单列主键使用 `=` 或 `IN (...)`，复合主键使用 `(a = 1 AND b = 2) OR ...`
+    fn keys_condition(&self, keys: &[Vec<String>]) -> String {
+        if let ([pk], [key]) = (self.primary_keys.as_slice(), keys) {
+            return format!("{} = {}", self.dialect.quote_identifier(pk), key[0]);
+        }
+        if let [pk] = self.primary_keys.as_slice() {
+            let values: Vec<&str> = keys.iter().map(|k| k[0].as_str()).collect();
+            return format!(
+                "{} IN ({})",
+                self.dialect.quote_identifier(pk),
+                values.join(", ")
+            );
+        }
+
+        let groups: Vec<String> = keys
+            .iter()
+            .map(|key| {
+                let conditions: Vec<String> = self
+                    .primary_keys
+                    .iter()
+                    .zip(key)
+                    .map(|(pk, val)| format!("{} = {}", self.dialect.quote_identifier(pk), val))
+                    .collect();
+                format!("({})", conditions.join(" AND "))
+            })
+            .collect();
+        groups.join("\n   OR ")
+    }
+
+    /// 取出一行的主键值；主键为空时报错，`= NULL` 不会匹配任何行
+    fn key_values(
+        &self,
+        key_indices: &[usize],
+        column_names: &[String],
+        values: &[String],
+        row: &str,
+    ) -> Result<Vec<String>> {
+        if let Some(&i) = key_indices.iter().find(|&&i| values[i] == "NULL") {
+            return Err(ExcelCliError::ExportError(format!(
+                "{}的主键列 '{}' 为空，无法按主键删除或同步",
+                row, column_names[i]
+            )));
+        }
+        Ok(key_indices.iter().map(|&i| values[i].clone()).collect())
+    }
+
+    /// 按批量大小写入 DELETE 语句
+    fn write_deletes(&self, file: &mut SqlWriter, keys: &[Vec<String>]) -> Result<()> {
+        for chunk in keys.chunks(self.batch_size.max(1)) {
+            file.statement(&format!(
+                "DELETE FROM {} WHERE {};",
+                self.dialect.quote_table_name(&self.table_name),
+                self.keys_condition(chunk)
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// 导出 DELETE 语句
+    fn export_delete(
+        &self,
+        data: &ExcelData,
+        file: &mut SqlWriter,
+        column_names: &[String],
+    ) -> Result<()> {
+        let key_indices = self.primary_key_indices(column_names)?;
+
+        let keys: Vec<Vec<String>> = data
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let values = self.format_row(&data.headers, row);
+                self.key_values(
+                    &key_indices,
+                    column_names,
+                    &values,
+                    &format!("第 {} 行", i + 1),
+                )
+            })
+            .collect::<Result<_>>()?;
+
+        self.write_deletes(file, &keys)
+    }
+
+    /// 导出同步语句：先删除快照中已不存在的行，再更新有变化的行，最后插入新增行
+    ///
+    /// 与 `DataDiff` 一样按显示文本对比，数值 `1` 与文本 `"1"` 视为相同
+    fn export_sync(
+        &self,
+        data: &ExcelData,
+        file: &mut SqlWriter,
+        column_names: &[String],
+    ) -> Result<()> {
+        if let Some(diff) = &self.diff {
+            return self.write_diff(diff, &data.headers, column_names, file);
+        }
+
+        let keys: Vec<String> = self
+            .primary_key_indices(column_names)?
+            .into_iter()
+            .map(|i| data.headers[i].clone())
+            .collect();
+        let mut snapshot = ExcelData::new(data.sheet_name.clone(), data.headers.clone());
+        snapshot.rows = self.snapshot.clone().unwrap_or_default();
+        // 快照与当前数据一样转换布尔、日期文本，避免只因写法不同而更新
+        let snapshot = self.normalize_values(&snapshot);
+        let diff = DataDiff::compare(&snapshot, data, &keys)?;
+        self.write_diff(&diff, &data.headers, column_names, file)
+    }
+
+    /// 把对比结果写成 DELETE/UPDATE/INSERT 语句，只包含 `headers` 中的列
+    fn write_diff(
+        &self,
+        diff: &DataDiff,
+        headers: &[String],
+        column_names: &[String],
+        file: &mut SqlWriter,
+    ) -> Result<()> {
+        let key_indices = self.primary_key_indices(column_names)?;
+
+        // 要写入的值整理为一份数据，以便按推断的列类型转换布尔、日期文本
+        let mut values = ExcelData::new(String::new(), headers.to_vec());
+        for (_, row) in diff.entries() {
+            let mut cells: HashMap<String, CellValue> = row
+                .changes
+                .iter()
+                .map(|change| (change.column.clone(), change.after.clone()))
+                .collect();
+            cells.extend(diff.key_columns.iter().cloned().zip(row.key_values.clone()));
+            values.add_row(ExcelRow { data: cells });
+        }
+        let values = self.normalize_values(&values);
+
+        let mut deletes = Vec::new();
+        let mut updates = Vec::new();
+        let mut inserts = Vec::new();
+        for ((kind, row), cells) in diff.entries().zip(&values.rows) {
+            let formatted = self.format_row(headers, cells);
+            let label = match kind {
+                "removed" => "删除",
+                "modified" => "修改",
+                _ => "新增",
+            };
+            let key = self.key_values(
+                &key_indices,
+                column_names,
+                &formatted,
+                &format!("{}的行 ({})", label, row.key.join(", ")),
+            )?;
+
+            match kind {
+                "removed" => deletes.push(key),
+                "added" => inserts.push(formatted),
+                _ => {
+                    let changed: Vec<String> = row
+                        .changes
+                        .iter()
+                        .filter_map(|change| headers.iter().position(|h| *h == change.column))
+                        .filter(|&i| {
+                            !key_indices.contains(&i)
+                                && self
+                                    .update_columns
+                                    .as_ref()
+                                    .is_none_or(|only| only.contains(&column_names[i]))
+                        })
+                        .map(|i| {
+                            format!(
+                                "{} = {}",
+                                self.dialect.quote_identifier(&column_names[i]),
+                                formatted[i]
+                            )
+                        })
+                        .collect();
+                    if !changed.is_empty() {
+                        updates.push((changed, key));
+                    }
+                }
+            }
+        }
+
+        writeln!(
+            file,
+            "-- Sync: {} deleted, {} updated, {} inserted",
+            deletes.len(),
+            updates.len(),
+            inserts.len()
+        )?;
+        writeln!(file)?;
+
+        self.write_deletes(file, &deletes)?;
+
+        let quoted_table = self.dialect.quote_table_name(&self.table_name);
+        for (set_clauses, key) in &updates {
+            file.statement(&format!(
+                "{} {} WHERE {};",
+                self.dialect.update_prefix(&quoted_table),
+                set_clauses.join(", "),
+                self.keys_condition(std::slice::from_ref(key))
+            ))?;
+        }
+
+        let use_batch = self.dialect.supports_multi_row_insert() && self.batch_size > 1;
+        if use_batch {
+            for chunk in inserts.chunks(self.batch_size) {
+                let sql = self.generate_batch_insert(&self.table_name, column_names, chunk);
+                file.statement(&sql)?;
+            }
+        } else {
+            for values in &inserts {
+                let sql = self.generate_single_insert(&self.table_name, column_names, values);
+                file.statement(&sql)?;
+            }
         }
 
         Ok(())
@@ -607,6 +1843,51 @@ mod tests {
         );
         assert_eq!(SqlDialect::SQLite.quote_identifier("table"), "\"table\"");
         assert_eq!(SqlDialect::SqlServer.quote_identifier("table"), "[table]");
+
+        // 内嵌的引用字符需要转义
+        assert_eq!(SqlDialect::MySQL.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(
+            SqlDialect::PostgreSQL.quote_identifier("a\"b"),
+            "\"a\"\"b\""
+        );
+        assert_eq!(SqlDialect::SqlServer.quote_identifier("a]b"), "[a]]b]");
+        assert_eq!(
+            SqlDialect::SqlServer.quote_table_name("db.dbo.users"),
+            "[db].[dbo].[users]"
+        );
+        assert_eq!(
+            SqlDialect::Oracle.quote_table_name("hr.users"),
+            "\"HR\".\"USERS\""
+        );
+    }
+
+    #[test]
+    fn test_quote_string_per_dialect() {
+        let value = "it's a\\b\0";
+        assert_eq!(SqlDialect::MySQL.quote_string(value), "'it''s a\\\\b\\0'");
+        assert_eq!(SqlDialect::SQLite.quote_string("it's a\\b"), "'it''s a\\b'");
+        assert_eq!(
+            SqlDialect::PostgreSQL.quote_string("a\nb\\"),
+            "E'a\\nb\\\\'"
+        );
+        assert_eq!(SqlDialect::SqlServer.quote_string("O'Neil"), "'O''Neil'");
+        assert_eq!(SqlDialect::SqlServer.quote_string("张三"), "N'张三'");
+        assert_eq!(SqlDialect::BigQuery.quote_string("it's\n"), "'it\\'s\\n'");
+
+        // 无法表示 NUL 的方言报错，而不是静默删除
+        let mut data = ExcelData::new("Sheet1".to_string(), vec!["Name".to_string()]);
+        let mut row = HashMap::new();
+        row.insert("Name".to_string(), CellValue::String(value.to_string()));
+        data.add_row(ExcelRow { data: row });
+        let pg = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string());
+        let err = pg.check_nul(&data).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("第 1 行 'Name' 列的值包含 NUL 字符"));
+        let mysql = SqlExporter::new(SqlDialect::MySQL, "t".to_string());
+        assert!(mysql.check_nul(&data).is_ok());
+        let mysql = SqlExporter::new(SqlDialect::MySQL, "t\0".to_string());
+        assert!(mysql.check_nul(&data).is_err());
     }
 
     #[test]
@@ -644,6 +1925,134 @@ mod tests {
     }
 
     #[test]
+    fn test_prepared_statements() {
+        let headers = vec!["id".to_string(), "name".to_string(), "score".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        let mut row = HashMap::new();
+        row.insert("id".to_string(), CellValue::Number(1.0));
+        row.insert(
+            "name".to_string(),
+            CellValue::String("x'); DROP TABLE t;--".to_string()),
+        );
+        row.insert("score".to_string(), CellValue::Number(9.5));
+        data.add_row(ExcelRow { data: row });
+
+        let insert = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string())
+            .prepared_statements(&data)
+            .unwrap();
+        assert_eq!(
+            insert[0].sql,
+            "INSERT INTO \"t\" (\"id\", \"name\", \"score\") VALUES ($1, $2, $3);"
+        );
+        assert_eq!(
+            insert[0].params,
+            vec![
+                SqlParam::Int(1),
+                SqlParam::Text("x'); DROP TABLE t;--".to_string()),
+                SqlParam::Float(9.5)
+            ]
+        );
+
+        // UPDATE 的参数按占位符出现的顺序排列：先 SET，后 WHERE
+        let update = SqlExporter::new(SqlDialect::MySQL, "t".to_string())
+            .with_mode(SqlMode::Update)
+            .with_primary_keys(vec!["id".to_string()])
+            .with_update_columns(vec!["score".to_string()])
+            .prepared_statements(&data)
+            .unwrap();
+        assert_eq!(update[0].sql, "UPDATE `t` SET `score` = ? WHERE `id` = ?;");
+        assert_eq!(
+            update[0].params,
+            vec![SqlParam::Float(9.5), SqlParam::Int(1)]
+        );
+
+        let delete = SqlExporter::new(SqlDialect::SqlServer, "t".to_string())
+            .with_mode(SqlMode::Delete)
+            .with_primary_keys(vec!["id".to_string(), "name".to_string()])
+            .prepared_statements(&data)
+            .unwrap();
+        assert_eq!(
+            delete[0].sql,
+            "DELETE FROM [t] WHERE ([id] = @p1 AND [name] = @p2);"
+        );
+
+        let upsert = SqlExporter::new(SqlDialect::Oracle, "t".to_string())
+            .with_mode(SqlMode::Upsert)
+            .with_primary_keys(vec!["id".to_string()])
+            .prepared_statements(&data)
+            .unwrap();
+        assert!(upsert[0]
+            .sql
+            .contains("SELECT :1 AS \"ID\", :2 AS \"NAME\", :3 AS \"SCORE\""));
+        assert_eq!(upsert[0].params.len(), 3);
+
+        let copy = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string())
+            .with_mode(SqlMode::Copy)
+            .prepared_statements(&data);
+        assert!(copy.is_err());
+    }
+
+    #[test]
+    fn test_normalize_values_is_opt_in() {
+        let data = ExcelData::from_rows(
+            "s",
+            &["flag", "born"],
+            &[&[CellValue::text("yes"), CellValue::text("2024.03.01")]],
+        );
+        let exporter = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string());
+        assert_eq!(
+            exporter.prepared_statements(&data).unwrap()[0].params,
+            vec![
+                SqlParam::Text("yes".to_string()),
+                SqlParam::Text("2024.03.01".to_string())
+            ]
+        );
+        assert_eq!(
+            exporter
+                .with_normalize_values(true)
+                .prepared_statements(&data)
+                .unwrap()[0]
+                .params,
+            vec![
+                SqlParam::Bool(true),
+                SqlParam::Text("2024-03-01".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalized_identifiers() {
+        let headers = vec!["Order ID".to_string(), "客户名称".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        let mut row = HashMap::new();
+        row.insert("Order ID".to_string(), CellValue::Number(1.0));
+        row.insert(
+            "客户名称".to_string(),
+            CellValue::String("张三".to_string()),
+        );
+        data.add_row(ExcelRow { data: row });
+
+        let normalizer = IdentifierNormalizer::new(SqlDialect::PostgreSQL)
+            .with_case(crate::identifier::IdentifierCase::Snake)
+            .with_renames(vec![("客户名称".to_string(), "customer".to_string())]);
+        let exporter = SqlExporter::new(SqlDialect::PostgreSQL, "Sales Orders".to_string())
+            .with_mode(SqlMode::Upsert)
+            .with_primary_keys(vec!["Order ID".to_string()])
+            .with_normalizer(normalizer);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("normalized.sql");
+        let path = path.to_str().unwrap();
+        exporter.export(&data, path).unwrap();
+        let sql = std::fs::read_to_string(path).unwrap();
+        assert!(sql.contains(
+            "INSERT INTO \"sales_orders\" (\"order_id\", \"customer\") VALUES (1, '张三') \
+             ON CONFLICT (\"order_id\") DO UPDATE SET \"customer\" = EXCLUDED.\"customer\";"
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
     fn test_format_values() {
         let dialect = SqlDialect::MySQL;
 
@@ -656,4 +2065,372 @@ mod tests {
         assert_eq!(dialect.format_value(&CellValue::Boolean(true)), "1");
         assert_eq!(dialect.format_value(&CellValue::Empty), "NULL");
     }
+
+    #[test]
+    fn test_copy_mode() {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["Name".to_string(), "Active".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert(
+            "Name".to_string(),
+            CellValue::String("a\tb\\c\nd".to_string()),
+        );
+        row1.insert("Active".to_string(), CellValue::Boolean(true));
+        data.add_row(ExcelRow { data: row1 });
+        data.add_row(ExcelRow {
+            data: HashMap::new(),
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("load.sql");
+        let path = path.to_str().unwrap();
+
+        SqlExporter::new(SqlDialect::PostgreSQL, "users".to_string())
+            .with_mode(SqlMode::Copy)
+            .with_column_mapping(vec!["name".to_string(), "active".to_string()])
+            .export(&data, path)
+            .unwrap();
+        let sql = std::fs::read_to_string(path).unwrap();
+        assert!(sql.contains(
+            "COPY \"users\" (\"name\", \"active\") FROM stdin;\na\\tb\\\\c\\nd\tt\n\\N\t\\N\n\\.\n"
+        ));
+
+        SqlExporter::new(SqlDialect::MySQL, "users".to_string())
+            .with_mode(SqlMode::Copy)
+            .export(&data, path)
+            .unwrap();
+        let sql = std::fs::read_to_string(path).unwrap();
+        assert!(sql.contains("LOAD DATA LOCAL INFILE"));
+        assert!(sql.contains("(`Name`, `Active`);"));
+        let rows = std::fs::read_to_string(dir.path().join("load.data.tsv")).unwrap();
+        assert_eq!(rows, "a\\tb\\\\c\\nd\t1\n\\N\t\\N\n");
+    }
+
+    fn sync_row(id: f64, region: &str, name: &str) -> ExcelRow {
+        let mut row = HashMap::new();
+        row.insert("id".to_string(), CellValue::Number(id));
+        row.insert("region".to_string(), CellValue::String(region.to_string()));
+        row.insert("name".to_string(), CellValue::String(name.to_string()));
+        ExcelRow { data: row }
+    }
+
+    fn render(exporter: &SqlExporter, data: &ExcelData) -> String {
+        let mut buf = Vec::new();
+        let mut file = SqlWriter {
+            out: &mut buf,
+            begin: None,
+            commit_every: None,
+            count: 0,
+        };
+        let columns = exporter.get_column_names(data);
+        match exporter.mode {
+            SqlMode::Delete => exporter.export_delete(data, &mut file, &columns).unwrap(),
+            _ => exporter.export_sync(data, &mut file, &columns).unwrap(),
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// 按导出器的模式生成语句，不含文件头
+    fn render_mode(exporter: &SqlExporter, data: &ExcelData) -> String {
+        let mut buf = Vec::new();
+        let mut file = SqlWriter {
+            out: &mut buf,
+            begin: None,
+            commit_every: None,
+            count: 0,
+        };
+        let columns = exporter.get_column_names(data);
+        match exporter.mode {
+            SqlMode::Update => exporter.export_update(data, &mut file, &columns).unwrap(),
+            _ => exporter.export_upsert(data, &mut file, &columns).unwrap(),
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_delete_mode() {
+        let headers = vec!["id".to_string(), "region".to_string(), "name".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        for id in 1..=3 {
+            data.add_row(sync_row(id as f64, "cn", "x"));
+        }
+
+        let exporter = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string())
+            .with_mode(SqlMode::Delete)
+            .with_primary_keys(vec!["id".to_string()])
+            .with_batch_size(2);
+        assert_eq!(
+            render(&exporter, &data),
+            "DELETE FROM \"t\" WHERE \"id\" IN (1, 2);\nDELETE FROM \"t\" WHERE \"id\" = 3;\n"
+        );
+
+        let exporter = exporter.with_primary_keys(vec!["id".to_string(), "region".to_string()]);
+        assert!(render(&exporter, &data).starts_with(
+            "DELETE FROM \"t\" WHERE (\"id\" = 1 AND \"region\" = 'cn')\n   OR (\"id\" = 2 AND \"region\" = 'cn');\n"
+        ));
+
+        // 主键为空的行不能写成 `= NULL`
+        data.rows[1]
+            .data
+            .insert("region".to_string(), CellValue::Empty);
+        let mut buf = Vec::new();
+        let mut file = SqlWriter {
+            out: &mut buf,
+            begin: None,
+            commit_every: None,
+            count: 0,
+        };
+        let columns = exporter.get_column_names(&data);
+        let err = exporter
+            .export_delete(&data, &mut file, &columns)
+            .unwrap_err();
+        assert!(err.to_string().contains("第 2 行的主键列 'region' 为空"));
+    }
+
+    #[test]
+    fn test_sync_mode() {
+        let headers = vec!["id".to_string(), "region".to_string(), "name".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        data.add_row(sync_row(1.0, "cn", "Alice"));
+        data.add_row(sync_row(2.0, "cn", "Bobby"));
+        data.add_row(sync_row(4.0, "us", "Dave"));
+
+        let snapshot = vec![
+            sync_row(1.0, "cn", "Alice"),
+            sync_row(2.0, "cn", "Bob"),
+            sync_row(3.0, "cn", "Carol"),
+        ];
+
+        let exporter = SqlExporter::new(SqlDialect::MySQL, "t".to_string())
+            .with_mode(SqlMode::Sync)
+            .with_primary_keys(vec!["id".to_string()])
+            .with_snapshot(snapshot);
+        let sql = render(&exporter, &data);
+        assert!(sql.starts_with("-- Sync: 1 deleted, 1 updated, 1 inserted\n"));
+        assert!(sql.contains("DELETE FROM `t` WHERE `id` = 3;\n"));
+        assert!(sql.contains("UPDATE `t` SET `name` = 'Bobby' WHERE `id` = 2;\n"));
+        assert!(sql.contains("INSERT INTO `t` (`id`, `region`, `name`) VALUES\n(4, 'us', 'Dave');"));
+        assert!(!sql.contains("Alice"));
+
+        // 与 DataDiff 一样按显示文本对比：文本 "1" 与数值 1 是同一行
+        let mut snapshot = vec![sync_row(1.0, "cn", "Alice"), sync_row(2.0, "cn", "Bob")];
+        snapshot[0]
+            .data
+            .insert("id".to_string(), CellValue::String("1".to_string()));
+        let exporter = exporter.with_snapshot(snapshot.clone());
+        let sql = render(&exporter, &data);
+        assert!(sql.starts_with("-- Sync: 0 deleted, 1 updated, 1 inserted\n"));
+
+        // 传入 diff 命令算好的结果时生成相同的语句
+        let mut old = ExcelData::new("Sheet1".to_string(), data.headers.clone());
+        old.rows = snapshot;
+        let keys = vec!["id".to_string()];
+        let diff = DataDiff::compare(&old, &data, &keys).unwrap();
+        let from_diff = SqlExporter::new(SqlDialect::MySQL, "t".to_string())
+            .with_mode(SqlMode::Sync)
+            .with_primary_keys(keys)
+            .with_diff(diff);
+        assert_eq!(render(&from_diff, &data), sql);
+    }
+
+    #[test]
+    fn test_transaction_wrapping() {
+        let mut data = ExcelData::new("Sheet1".to_string(), vec!["id".to_string()]);
+        for id in 1..=3 {
+            let mut row = HashMap::new();
+            row.insert("id".to_string(), CellValue::Number(id as f64));
+            data.add_row(ExcelRow { data: row });
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tx.sql");
+        let path = path.to_str().unwrap();
+
+        SqlExporter::new(SqlDialect::SqlServer, "t".to_string())
+            .with_commit_every(2)
+            .with_stop_on_error(true)
+            .with_clear_table(ClearTable::Truncate)
+            .with_identity_insert(true)
+            .export(&data, path)
+            .unwrap();
+        let sql = std::fs::read_to_string(path).unwrap();
+        assert!(sql.ends_with(
+            "SET XACT_ABORT ON;\nBEGIN TRANSACTION;\nTRUNCATE TABLE [t];\nSET IDENTITY_INSERT [t] ON;\n\n\
+             INSERT INTO [t] ([id]) VALUES (1);\nINSERT INTO [t] ([id]) VALUES (2);\n\
+             COMMIT;\nBEGIN TRANSACTION;\nINSERT INTO [t] ([id]) VALUES (3);\n\
+             SET IDENTITY_INSERT [t] OFF;\nCOMMIT;\n"
+        ));
+
+        // Oracle 没有显式的 BEGIN，SQLite 没有 TRUNCATE
+        SqlExporter::new(SqlDialect::Oracle, "t".to_string())
+            .with_transaction(true)
+            .export(&data, path)
+            .unwrap();
+        let sql = std::fs::read_to_string(path).unwrap();
+        assert!(!sql.contains("BEGIN"));
+        assert!(sql.ends_with("COMMIT;\n"));
+
+        SqlExporter::new(SqlDialect::SQLite, "t".to_string())
+            .with_clear_table(ClearTable::Truncate)
+            .export(&data, path)
+            .unwrap();
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .contains("DELETE FROM \"t\";\n"));
+
+        let result = SqlExporter::new(SqlDialect::MySQL, "t".to_string())
+            .with_identity_insert(true)
+            .export(&data, path);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_batched_update_and_upsert() {
+        let headers = vec!["id".to_string(), "region".to_string(), "name".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        data.add_row(sync_row(1.0, "cn", "A"));
+        data.add_row(sync_row(2.0, "us", "B"));
+        let columns = data.headers.clone();
+        let rows: Vec<Vec<String>> = data
+            .rows
+            .iter()
+            .map(|row| {
+                SqlExporter::new(SqlDialect::MySQL, "t".to_string()).format_row(&data.headers, row)
+            })
+            .collect();
+
+        let mysql = SqlExporter::new(SqlDialect::MySQL, "t".to_string())
+            .with_primary_keys(vec!["id".to_string()])
+            .with_update_columns(vec!["name".to_string()]);
+        assert_eq!(
+            mysql.generate_batch_update("t", &columns, &rows).unwrap(),
+            "UPDATE `t` SET\n    `name` = CASE WHEN `id` = 1 THEN 'A' WHEN `id` = 2 THEN 'B' ELSE `name` END\nWHERE `id` IN (1, 2);"
+        );
+        assert_eq!(
+            mysql.generate_upsert("t", &columns, &rows).unwrap(),
+            "INSERT INTO `t` (`id`, `region`, `name`) VALUES\n(1, 'cn', 'A'),\n(2, 'us', 'B') ON DUPLICATE KEY UPDATE `region` = VALUES(`region`), `name` = VALUES(`name`);"
+        );
+
+        // 日期列的值是字符串字面量，类型由 ELSE 中的目标列决定
+        let pg = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string())
+            .with_primary_keys(vec!["id".to_string()]);
+        let pg_columns = vec!["id".to_string(), "born".to_string()];
+        let pg_rows = vec![
+            vec!["1".to_string(), "'2024-03-01'".to_string()],
+            vec!["2".to_string(), "NULL".to_string()],
+        ];
+        assert_eq!(
+            pg.generate_batch_update("t", &pg_columns, &pg_rows).unwrap(),
+            "UPDATE \"t\" SET\n    \"born\" = CASE WHEN \"id\" = 1 THEN '2024-03-01' WHEN \"id\" = 2 THEN NULL ELSE \"born\" END\nWHERE \"id\" IN (1, 2);"
+        );
+
+        let mssql = SqlExporter::new(SqlDialect::SqlServer, "t".to_string())
+            .with_primary_keys(vec!["id".to_string()]);
+        assert!(mssql
+            .generate_upsert("t", &columns, &rows)
+            .unwrap()
+            .contains(
+                "USING (VALUES (1, 'cn', 'A'), (2, 'us', 'B')) AS source ([id], [region], [name])"
+            ));
+
+        // 同一批中主键重复时另起一批，与逐行执行一样由后一行生效
+        data.add_row(sync_row(1.0, "cn", "C"));
+        data.add_row(sync_row(3.0, "us", "D"));
+        let upsert = pg.clone().with_mode(SqlMode::Upsert);
+        let sql = render_mode(&upsert, &data);
+        assert_eq!(sql.matches("INSERT INTO").count(), 2);
+        assert!(sql.contains("(1, 'cn', 'A'),\n(2, 'us', 'B') ON CONFLICT"));
+        assert!(sql.contains("(1, 'cn', 'C'),\n(3, 'us', 'D') ON CONFLICT"));
+        let update = pg.with_mode(SqlMode::Update);
+        let sql = render_mode(&update, &data);
+        assert_eq!(sql.matches("UPDATE \"t\" SET").count(), 2);
+        assert!(sql.contains("WHEN \"id\" = 1 THEN 'C'"));
+    }
+
+    #[test]
+    fn test_merge_per_dialect() {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let rows = vec![vec!["1".to_string(), "'A'".to_string()]];
+
+        let oracle = SqlExporter::new(SqlDialect::Oracle, "t".to_string())
+            .with_primary_keys(vec!["id".to_string()]);
+        assert_eq!(
+            oracle.generate_upsert("t", &columns, &rows).unwrap(),
+            "MERGE INTO \"T\" target\nUSING (SELECT 1 AS \"ID\", 'A' AS \"NAME\" FROM DUAL) source\n\
+             ON (target.\"ID\" = source.\"ID\")\nWHEN MATCHED THEN UPDATE SET target.\"NAME\" = source.\"NAME\"\n\
+             WHEN NOT MATCHED THEN INSERT (\"ID\", \"NAME\") VALUES (source.\"ID\", source.\"NAME\");"
+        );
+
+        let mssql = SqlExporter::new(SqlDialect::SqlServer, "t".to_string())
+            .with_primary_keys(vec!["id".to_string()]);
+        let sql = mssql.generate_upsert("t", &columns, &rows).unwrap();
+        assert!(sql.starts_with("MERGE INTO [t] WITH (HOLDLOCK) AS target\nUSING (VALUES (1, 'A')) AS source ([id], [name])"));
+        assert!(sql.ends_with(";"));
+
+        // 所有列都是主键：不生成空的 UPDATE SET
+        let all_keys = vec!["id".to_string(), "name".to_string()];
+        let mssql = mssql.with_primary_keys(all_keys.clone());
+        assert!(!mssql
+            .generate_upsert("t", &columns, &rows)
+            .unwrap()
+            .contains("WHEN MATCHED"));
+        let pg = SqlExporter::new(SqlDialect::PostgreSQL, "t".to_string())
+            .with_primary_keys(all_keys.clone());
+        assert!(pg
+            .generate_upsert("t", &columns, &rows)
+            .unwrap()
+            .ends_with("ON CONFLICT (\"id\", \"name\") DO NOTHING;"));
+        let mysql =
+            SqlExporter::new(SqlDialect::MySQL, "t".to_string()).with_primary_keys(all_keys);
+        assert!(mysql
+            .generate_upsert("t", &columns, &rows)
+            .unwrap()
+            .ends_with("ON DUPLICATE KEY UPDATE `id` = `id`;"));
+    }
+
+    #[test]
+    fn test_warehouse_dialects() {
+        assert_eq!(
+            SqlDialect::ClickHouse.format_value(&CellValue::String("it's \\".to_string())),
+            "'it\\'s \\\\'"
+        );
+        assert_eq!(
+            SqlDialect::BigQuery.format_value(&CellValue::Boolean(true)),
+            "TRUE"
+        );
+
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let rows = vec![vec!["1".to_string(), "'A'".to_string()]];
+        let keys = vec!["id".to_string()];
+
+        let duckdb =
+            SqlExporter::new(SqlDialect::DuckDB, "t".to_string()).with_primary_keys(keys.clone());
+        assert_eq!(
+            duckdb.generate_upsert("t", &columns, &rows).unwrap(),
+            "INSERT OR REPLACE INTO \"t\" (\"id\", \"name\") VALUES (1, 'A');"
+        );
+
+        let bigquery =
+            SqlExporter::new(SqlDialect::BigQuery, "t".to_string()).with_primary_keys(keys.clone());
+        let sql = bigquery.generate_upsert("t", &columns, &rows).unwrap();
+        assert!(sql.contains("USING (SELECT 1 AS `id`, 'A' AS `name`) AS source"));
+        assert!(sql.contains("WHEN MATCHED THEN UPDATE SET `name` = source.`name`"));
+
+        let clickhouse =
+            SqlExporter::new(SqlDialect::ClickHouse, "t".to_string()).with_primary_keys(keys);
+        assert_eq!(
+            clickhouse
+                .generate_update(
+                    "t",
+                    &columns,
+                    &rows[0],
+                    &ExcelData::new(String::new(), vec![])
+                )
+                .unwrap(),
+            "ALTER TABLE `t` UPDATE `name` = 'A' WHERE `id` = 1;"
+        );
+    }
 }
@@ -0,0 +1,294 @@
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+
+use crate::error::Result;
+use crate::exporter::sql::SqlDialect;
+use crate::exporter::Exporter;
+use crate::models::{CellValue, ExcelData};
+use crate::schema::{SchemaGenerator, TypeInference};
+
+/// SQLite 数据库文件导出器
+///
+/// 每个导出的工作表对应数据库中的一张表；写入已有数据库时会替换同名表，其他表保持不变
+pub struct SqliteDbExporter {
+    table_name: Option<String>,
+    primary_keys: Vec<String>,
+    indexes: Vec<Vec<String>>,
+}
+
+impl SqliteDbExporter {
+    /// 创建新的 SQLite 数据库导出器（默认使用工作表名作为表名）
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            primary_keys: Vec::new(),
+            indexes: Vec::new(),
+        }
+    }
+
+    /// 设置表名
+    pub fn with_table_name(mut self, table_name: String) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// 设置主键列
+    pub fn with_primary_keys(mut self, keys: Vec<String>) -> Self {
+        self.primary_keys = keys;
+        self
+    }
+
+    /// 添加索引，每个索引可以包含多列
+    pub fn with_index(mut self, columns: Vec<String>) -> Self {
+        self.indexes.push(columns);
+        self
+    }
+
+    /// 创建 schema 生成器（复用 SchemaGenerator 的 SQLite DDL，复合主键生成表约束）
+    ///
+    /// 只有显式设置的表名按 `.` 解析为限定名，来自工作表名的表名整体引用
+    fn schema_generator(&self, table: &str) -> SchemaGenerator {
+        let mut generator = SchemaGenerator::new(SqlDialect::SQLite, table.to_string())
+            .with_if_not_exists(false)
+            .with_qualified_table_name(self.table_name.is_some())
+            .with_primary_keys(self.primary_keys.clone());
+        for columns in &self.indexes {
+            generator = generator.with_index(columns.clone());
+        }
+        generator
+    }
+}
+
+impl Default for SqliteDbExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for SqliteDbExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        let table = self
+            .table_name
+            .clone()
+            .unwrap_or_else(|| data.sheet_name.clone());
+        let dialect = SqlDialect::SQLite;
+        let generator = self.schema_generator(&table);
+        let schema = generator.infer(data)?;
+        // 布尔、日期文本按推断的列类型写入
+        let data = TypeInference::normalize_values(data);
+        let data = data.as_ref();
+        // 所有语句使用同一种表名引用方式
+        let quoted_table = generator.quote_table(&schema.name);
+
+        let mut conn = Connection::open(output_path)?;
+        let tx = conn.transaction()?;
+
+        tx.execute_batch(&format!("DROP TABLE IF EXISTS {};", quoted_table))?;
+        tx.execute_batch(&generator.create_table_sql(&schema))?;
+
+        // 批量插入：单个事务 + 预编译语句
+        {
+            let columns: Vec<String> = data
+                .headers
+                .iter()
+                .map(|h| dialect.quote_identifier(h))
+                .collect();
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let mut stmt = tx.prepare(&format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                quoted_table,
+                columns.join(", "),
+                placeholders
+            ))?;
+
+            for row in &data.rows {
+                let values = data
+                    .headers
+                    .iter()
+                    .map(|h| to_sqlite_value(row.data.get(h).unwrap_or(&CellValue::Empty)));
+                stmt.execute(params_from_iter(values))?;
+            }
+        }
+
+        // 插入数据后再建索引
+        for sql in generator.create_index_sqls(&schema) {
+            tx.execute_batch(&sql)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "SQLite Database"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "sqlite"
+    }
+}
+
+/// 将单元格值转换为 SQLite 值
+fn to_sqlite_value(value: &CellValue) -> Value {
+    match value {
+        CellValue::String(s) => Value::Text(s.clone()),
+        CellValue::Number(n) => {
+            if n.fract() == 0.0 && n.is_finite() && n.abs() < 9.0e15 {
+                Value::Integer(*n as i64)
+            } else {
+                Value::Real(*n)
+            }
+        }
+        CellValue::Boolean(b) => Value::Integer(*b as i64),
+        CellValue::Empty => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExcelRow;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sqlite_db_export() {
+        let mut data = ExcelData::new(
+            "users".to_string(),
+            vec!["id".to_string(), "name".to_string()],
+        );
+
+        for (id, name) in [(1.0, "Alice"), (2.0, "Bob")] {
+            let mut row = HashMap::new();
+            row.insert("id".to_string(), CellValue::Number(id));
+            row.insert("name".to_string(), CellValue::String(name.to_string()));
+            data.add_row(ExcelRow { data: row });
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.sqlite");
+        let path = path.to_str().unwrap();
+
+        let exporter = SqliteDbExporter::new()
+            .with_primary_keys(vec!["id".to_string()])
+            .with_index(vec!["name".to_string()]);
+        exporter.export(&data, path).unwrap();
+        // 再次导出会替换同名表而不是重复插入
+        exporter.export(&data, path).unwrap();
+
+        let conn = Connection::open(path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM users", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let name: String = conn
+            .query_row("SELECT name FROM users WHERE id = 2", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(name, "Bob");
+
+        // 含 '.' 的工作表名整体作为表名
+        data.sheet_name = "Q1.2024".to_string();
+        exporter.export(&data, path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM \"Q1.2024\"", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let indexes: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_users_name'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexes, 1);
+    }
+
+    /// 查询单个整数
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |r| r.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_sheets_share_database() {
+        let users = ExcelData::from_rows(
+            "users",
+            &["id", "name"],
+            &[
+                &[CellValue::Number(1.0), CellValue::text("Alice")],
+                &[CellValue::Number(2.0), CellValue::text("Bob")],
+            ],
+        );
+        let orders = ExcelData::from_rows(
+            "orders",
+            &["oid", "user_id", "day"],
+            &[&[
+                CellValue::Number(10.0),
+                CellValue::Number(1.0),
+                CellValue::text("2024-03-01"),
+            ]],
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.sqlite");
+        let path = path.to_str().unwrap();
+
+        SqliteDbExporter::new()
+            .with_primary_keys(vec!["id".to_string()])
+            .export(&users, path)
+            .unwrap();
+        SqliteDbExporter::new()
+            .with_index(vec!["user_id".to_string(), "day".to_string()])
+            .export(&orders, path)
+            .unwrap();
+
+        let conn = Connection::open(path).unwrap();
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM users"), 2);
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM orders"), 1);
+        assert_eq!(
+            count(
+                &conn,
+                "SELECT COUNT(*) FROM pragma_index_info('idx_orders_user_id_day')"
+            ),
+            2
+        );
+        // 主键列不允许重复
+        assert!(conn
+            .execute("INSERT INTO users (id, name) VALUES (1, 'Carol')", [])
+            .is_err());
+    }
+
+    #[test]
+    fn test_replace_table_in_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("existing.sqlite");
+        let path = path.to_str().unwrap();
+        {
+            let conn = Connection::open(path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE other (x INTEGER); INSERT INTO other VALUES (7);
+                 CREATE TABLE users (legacy TEXT); INSERT INTO users VALUES ('old');",
+            )
+            .unwrap();
+        }
+
+        let users = ExcelData::from_rows(
+            "users",
+            &["id", "name"],
+            &[&[CellValue::Number(1.0), CellValue::text("Alice")]],
+        );
+        SqliteDbExporter::new().export(&users, path).unwrap();
+
+        // 同名表按新的结构重建，其他表保持不变
+        let conn = Connection::open(path).unwrap();
+        assert_eq!(count(&conn, "SELECT COUNT(*) FROM users WHERE id = 1"), 1);
+        assert_eq!(
+            count(
+                &conn,
+                "SELECT COUNT(*) FROM pragma_table_info('users') WHERE name = 'legacy'"
+            ),
+            0
+        );
+        assert_eq!(count(&conn, "SELECT x FROM other"), 7);
+    }
+}
@@ -1,10 +1,9 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Write;
 
 use serde_json::Value as JsonValue;
 use tera::{Context, Tera};
 
+use crate::encoding::TextEncoder;
 use crate::error::{ExcelCliError, Result};
 use crate::exporter::Exporter;
 use crate::models::{CellValue, ExcelData};
@@ -16,14 +15,15 @@ pub enum BuiltinTemplate {
     HtmlTable,
     /// Markdown 表格
     MarkdownTable,
-    /// XML
+    /// XML（示例模板，`xml` 格式使用专用的 XmlExporter）
     Xml,
-    /// YAML
+    /// YAML（示例模板，`yaml` 格式使用专用的 YamlExporter）
     Yaml,
 }
 
 impl BuiltinTemplate {
     /// 从字符串解析
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "html" | "html-table" => Ok(BuiltinTemplate::HtmlTable),
@@ -63,6 +63,7 @@ pub struct TemplateExporter {
     template_content: String,
     template_name: String,
     file_ext: String,
+    encoder: TextEncoder,
 }
 
 impl TemplateExporter {
@@ -88,6 +89,7 @@ impl TemplateExporter {
             template_content,
             template_name,
             file_ext,
+            encoder: TextEncoder::default(),
         })
     }
 
@@ -97,9 +99,16 @@ impl TemplateExporter {
             template_content: builtin.get_template().to_string(),
             template_name: format!("{:?}", builtin),
             file_ext: builtin.file_extension().to_string(),
+            encoder: TextEncoder::default(),
         }
     }
 
+    /// 设置输出编码
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
     /// 将 ExcelData 转换为 Tera Context
     fn create_context(&self, data: &ExcelData) -> Context {
         let mut context = Context::new();
@@ -140,11 +149,24 @@ impl TemplateExporter {
         context.insert("rows", &rows);
         context.insert("row_count", &data.row_count());
         context.insert("column_count", &data.column_count());
+        context.insert("encoding", self.encoder.encoding().name());
 
         context
     }
 }
 
+/// 渲染内置的 HTML 报告模板
+///
+/// 模板以 `.html` 结尾的名称注册，Tera 会自动转义 HTML 特殊字符
+pub(crate) fn render_html_report(name: &str, source: &str, context: &Context) -> Result<String> {
+    let name = format!("{}.html", name);
+    let mut tera = Tera::default();
+    tera.add_raw_template(&name, source)
+        .map_err(|e| ExcelCliError::ExportError(format!("模板解析失败: {}", e)))?;
+    tera.render(&name, context)
+        .map_err(|e| ExcelCliError::ExportError(format!("模板渲染失败: {}", e)))
+}
+
 impl Exporter for TemplateExporter {
     fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
         // 创建 Tera 实例
@@ -162,12 +184,8 @@ impl Exporter for TemplateExporter {
             .render(&self.template_name, &context)
             .map_err(|e| ExcelCliError::ExportError(format!("模板渲染失败: {}", e)))?;
 
-        // 写入文件
-        let mut file = File::create(output_path)?;
-        file.write_all(rendered.as_bytes())?;
-        file.flush()?;
-
-        Ok(())
+        // 转换编码并写入文件
+        self.encoder.write_file(output_path, &rendered)
     }
 
     fn format_name(&self) -> &'static str {
@@ -231,5 +249,9 @@ mod tests {
         assert!(context.get("sheet_name").is_some());
         assert!(context.get("headers").is_some());
         assert!(context.get("rows").is_some());
+        assert_eq!(
+            context.get("encoding").and_then(|v| v.as_str()),
+            Some("UTF-8")
+        );
     }
 }
@@ -0,0 +1,316 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::encoding::TextEncoder;
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::Exporter;
+use crate::models::{CellValue, ExcelData};
+
+/// XML 单元格输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlStyle {
+    /// 每个单元格输出为 `<row>` 的子元素
+    Elements,
+    /// 每个单元格输出为 `<row>` 的属性
+    Attributes,
+}
+
+impl XmlStyle {
+    /// 从字符串解析输出方式
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "elements" | "element" => Ok(XmlStyle::Elements),
+            "attributes" | "attribute" | "attrs" => Ok(XmlStyle::Attributes),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的 XML 输出方式: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// XML 导出器
+///
+/// 对文本和属性值进行转义，并将表头转换为合法的 XML 名称
+pub struct XmlExporter {
+    style: XmlStyle,
+    encoder: TextEncoder,
+}
+
+impl XmlExporter {
+    /// 创建新的 XML 导出器（默认使用子元素）
+    pub fn new() -> Self {
+        Self {
+            style: XmlStyle::Elements,
+            encoder: TextEncoder::default(),
+        }
+    }
+
+    /// 设置单元格输出方式
+    pub fn with_style(mut self, style: XmlStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 设置输出编码
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// 将数据序列化为 XML 文本
+    fn render(&self, data: &ExcelData) -> String {
+        let names = xml_names(&data.headers);
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "<?xml version=\"1.0\" encoding=\"{}\"?>",
+            self.encoder.encoding().name()
+        );
+        let _ = writeln!(
+            out,
+            "<worksheet name=\"{}\">",
+            escape_attribute(&data.sheet_name)
+        );
+        out.push_str("    <meta>\n");
+        let _ = writeln!(out, "        <row_count>{}</row_count>", data.row_count());
+        let _ = writeln!(
+            out,
+            "        <column_count>{}</column_count>",
+            data.column_count()
+        );
+        out.push_str("    </meta>\n");
+
+        // 表头：记录原始列名与 XML 名称的对应关系
+        out.push_str("    <headers>\n");
+        for (header, name) in data.headers.iter().zip(&names) {
+            if header == name {
+                let _ = writeln!(out, "        <header>{}</header>", escape_text(header));
+            } else {
+                let _ = writeln!(
+                    out,
+                    "        <header key=\"{}\">{}</header>",
+                    name,
+                    escape_text(header)
+                );
+            }
+        }
+        out.push_str("    </headers>\n");
+
+        out.push_str("    <rows>\n");
+        for row in &data.rows {
+            match self.style {
+                XmlStyle::Elements => {
+                    out.push_str("        <row>\n");
+                    for (header, name) in data.headers.iter().zip(&names) {
+                        let original = if header == name {
+                            String::new()
+                        } else {
+                            format!(" name=\"{}\"", escape_attribute(header))
+                        };
+                        match row.data.get(header).unwrap_or(&CellValue::Empty) {
+                            CellValue::Empty => {
+                                let _ = writeln!(out, "            <{}{}/>", name, original);
+                            }
+                            value => {
+                                let _ = writeln!(
+                                    out,
+                                    "            <{}{}>{}</{}>",
+                                    name,
+                                    original,
+                                    escape_text(&value.to_string()),
+                                    name
+                                );
+                            }
+                        }
+                    }
+                    out.push_str("        </row>\n");
+                }
+                XmlStyle::Attributes => {
+                    out.push_str("        <row");
+                    for (header, name) in data.headers.iter().zip(&names) {
+                        match row.data.get(header).unwrap_or(&CellValue::Empty) {
+                            CellValue::Empty => {}
+                            value => {
+                                let _ = write!(
+                                    out,
+                                    " {}=\"{}\"",
+                                    name,
+                                    escape_attribute(&value.to_string())
+                                );
+                            }
+                        }
+                    }
+                    out.push_str("/>\n");
+                }
+            }
+        }
+        out.push_str("    </rows>\n");
+        out.push_str("</worksheet>\n");
+
+        out
+    }
+}
+
+impl Default for XmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for XmlExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        let xml = self.render(data);
+        self.encoder.write_file(output_path, &xml)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "XML"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "xml"
+    }
+}
+
+/// 为每个表头生成唯一且合法的 XML 名称
+fn xml_names(headers: &[String]) -> Vec<String> {
+    let mut used = HashSet::new();
+    headers
+        .iter()
+        .map(|header| {
+            let base = sanitize_xml_name(header);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// 将任意字符串转换为合法的 XML 名称（NCName）
+///
+/// 非法字符替换为 `_`；以数字、`-`、`.` 或保留前缀 `xml` 开头时添加 `_` 前缀
+fn sanitize_xml_name(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let needs_prefix = match result.chars().next() {
+        None => true,
+        Some(c) => c.is_numeric() || c == '-' || c == '.',
+    } || result.to_lowercase().starts_with("xml");
+
+    if needs_prefix {
+        result.insert(0, '_');
+    }
+
+    result
+}
+
+/// 转义元素文本内容
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\r' => out.push_str("&#13;"),
+            c => push_xml_char(&mut out, c),
+        }
+    }
+    out
+}
+
+/// 转义属性值（保留换行和制表符）
+fn escape_attribute(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            c => push_xml_char(&mut out, c),
+        }
+    }
+    out
+}
+
+/// 写入字符，XML 1.0 不允许的控制字符替换为 U+FFFD
+fn push_xml_char(out: &mut String, c: char) {
+    let allowed = matches!(c, '\t' | '\n' | '\r')
+        || ('\u{20}'..='\u{D7FF}').contains(&c)
+        || ('\u{E000}'..='\u{FFFD}').contains(&c)
+        || c >= '\u{10000}';
+    out.push(if allowed { c } else { '\u{FFFD}' });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExcelRow;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sanitize_xml_name() {
+        assert_eq!(sanitize_xml_name("Name"), "Name");
+        assert_eq!(sanitize_xml_name("Order Date (UTC)"), "Order_Date__UTC_");
+        assert_eq!(sanitize_xml_name("客户名称"), "客户名称");
+        assert_eq!(sanitize_xml_name("2024"), "_2024");
+        assert_eq!(sanitize_xml_name("xmlData"), "_xmlData");
+        assert_eq!(sanitize_xml_name(""), "_");
+        assert_eq!(
+            xml_names(&["a b".to_string(), "a_b".to_string()]),
+            vec!["a_b", "a_b_2"]
+        );
+    }
+
+    #[test]
+    fn test_xml_render_escaping() {
+        let mut data = ExcelData::new(
+            "R&D".to_string(),
+            vec!["Order Date".to_string(), "Note".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert(
+            "Order Date".to_string(),
+            CellValue::String("2024-01-01".to_string()),
+        );
+        row1.insert(
+            "Note".to_string(),
+            CellValue::String("<b>\"x\" & y</b>".to_string()),
+        );
+        data.add_row(ExcelRow { data: row1 });
+
+        let xml = XmlExporter::new().render(&data);
+        assert!(xml.contains("<worksheet name=\"R&amp;D\">"));
+        assert!(xml.contains("<header key=\"Order_Date\">Order Date</header>"));
+        assert!(xml.contains("<Order_Date name=\"Order Date\">2024-01-01</Order_Date>"));
+        assert!(xml.contains("<Note>&lt;b&gt;\"x\" &amp; y&lt;/b&gt;</Note>"));
+
+        let xml = XmlExporter::new()
+            .with_style(XmlStyle::Attributes)
+            .render(&data);
+        assert!(xml.contains(
+            "<row Order_Date=\"2024-01-01\" Note=\"&lt;b&gt;&quot;x&quot; &amp; y&lt;/b&gt;\"/>"
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Write;
+
+use crate::encoding::TextEncoder;
+use crate::error::Result;
+use crate::exporter::Exporter;
+use crate::models::{CellValue, ExcelData};
+
+/// YAML 导出器
+///
+/// 直接生成 YAML 文本，对需要的字符串加引号并转义，保证输出可以被解析
+pub struct YamlExporter {
+    encoder: TextEncoder,
+}
+
+impl YamlExporter {
+    /// 创建新的 YAML 导出器
+    pub fn new() -> Self {
+        Self {
+            encoder: TextEncoder::default(),
+        }
+    }
+
+    /// 设置输出编码
+    pub fn with_encoder(mut self, encoder: TextEncoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// 将数据序列化为 YAML 文本
+    fn render(&self, data: &ExcelData) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "sheet_name: {}", yaml_string(&data.sheet_name));
+        let _ = writeln!(out, "row_count: {}", data.row_count());
+        let _ = writeln!(out, "column_count: {}", data.column_count());
+
+        if data.rows.is_empty() {
+            out.push_str("rows: []\n");
+            return out;
+        }
+
+        out.push_str("rows:\n");
+        for row in &data.rows {
+            if data.headers.is_empty() {
+                out.push_str("  - {}\n");
+                continue;
+            }
+
+            for (idx, header) in data.headers.iter().enumerate() {
+                let prefix = if idx == 0 { "  - " } else { "    " };
+                let value = row.data.get(header).unwrap_or(&CellValue::Empty);
+                let _ = writeln!(
+                    out,
+                    "{}{}: {}",
+                    prefix,
+                    yaml_string(header),
+                    yaml_value(value)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+impl Default for YamlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for YamlExporter {
+    fn export(&self, data: &ExcelData, output_path: &str) -> Result<()> {
+        let yaml = self.render(data);
+        self.encoder.write_file(output_path, &yaml)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "yaml"
+    }
+}
+
+/// 将单元格值转换为 YAML 标量
+fn yaml_value(value: &CellValue) -> String {
+    match value {
+        CellValue::String(s) => yaml_string(s),
+        CellValue::Number(n) => {
+            if n.is_nan() {
+                ".nan".to_string()
+            } else if n.is_infinite() {
+                if *n > 0.0 {
+                    ".inf".to_string()
+                } else {
+                    "-.inf".to_string()
+                }
+            } else if n.fract() == 0.0 && n.abs() < 1e15 {
+                format!("{}", *n as i64)
+            } else {
+                format!("{}", n)
+            }
+        }
+        CellValue::Boolean(b) => b.to_string(),
+        CellValue::Empty => "null".to_string(),
+    }
+}
+
+/// 将字符串转换为 YAML 标量，必要时使用双引号并转义
+fn yaml_string(s: &str) -> String {
+    if needs_quotes(s) {
+        quote_yaml(s)
+    } else {
+        s.to_string()
+    }
+}
+
+/// 判断字符串作为普通（plain）标量是否会被误解析
+fn needs_quotes(s: &str) -> bool {
+    let first = match s.chars().next() {
+        Some(c) => c,
+        None => return true,
+    };
+
+    // 会被解析为 null、布尔值或特殊值的字符串（包括 YAML 1.1 的 yes/no/on/off）
+    const RESERVED: &[&str] = &[
+        "null", "~", "true", "false", "yes", "no", "y", "n", "on", "off", ".inf", "-.inf", "+.inf",
+        ".nan", "<<", "=",
+    ];
+    if RESERVED.contains(&s.to_lowercase().as_str()) {
+        return true;
+    }
+
+    // 以指示符开头，或可能被解析为数字、日期
+    if "-?:,[]{}#&*!|>'\"%@`".contains(first)
+        || first.is_ascii_digit()
+        || first == '+'
+        || first == '.'
+    {
+        return true;
+    }
+
+    // 首尾空白会被丢弃
+    if first.is_whitespace() || s.ends_with(char::is_whitespace) {
+        return true;
+    }
+
+    // ": " 和 " #" 会被解析为映射和注释，结尾的 ':' 也是映射
+    if s.contains(": ") || s.contains(" #") || s.ends_with(':') {
+        return true;
+    }
+
+    s.chars()
+        .any(|c| c.is_control() || matches!(c, '\u{85}' | '\u{2028}' | '\u{2029}' | '\u{FEFF}'))
+}
+
+/// 生成双引号 YAML 字符串
+fn quote_yaml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            '\u{85}' => out.push_str("\\N"),
+            '\u{2028}' => out.push_str("\\L"),
+            '\u{2029}' => out.push_str("\\P"),
+            c if c.is_control() || c == '\u{FEFF}' => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExcelRow;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_yaml_quoting() {
+        assert_eq!(yaml_string("Alice"), "Alice");
+        assert_eq!(yaml_string("北京"), "北京");
+        assert_eq!(yaml_string("a: b"), "\"a: b\"");
+        assert_eq!(yaml_string("x #y"), "\"x #y\"");
+        assert_eq!(yaml_string(" leading"), "\" leading\"");
+        assert_eq!(yaml_string("line1\nline2"), "\"line1\\nline2\"");
+        assert_eq!(yaml_string("yes"), "\"yes\"");
+        assert_eq!(yaml_string("2023-01-15"), "\"2023-01-15\"");
+        assert_eq!(yaml_string(""), "\"\"");
+        assert_eq!(yaml_string("say \"hi\""), "say \"hi\"");
+        assert_eq!(yaml_string("\"hi\""), "\"\\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn test_yaml_render() {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["Order Date".to_string(), "Age".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert(
+            "Order Date".to_string(),
+            CellValue::String("a: b".to_string()),
+        );
+        row1.insert("Age".to_string(), CellValue::Number(30.0));
+        data.add_row(ExcelRow { data: row1 });
+
+        let mut row2 = HashMap::new();
+        row2.insert("Age".to_string(), CellValue::Number(1.5));
+        data.add_row(ExcelRow { data: row2 });
+
+        let yaml = YamlExporter::new().render(&data);
+        assert_eq!(
+            yaml,
+            "sheet_name: Sheet1\nrow_count: 2\ncolumn_count: 2\nrows:\n  - Order Date: \"a: b\"\n    Age: 30\n  - Order Date: null\n    Age: 1.5\n"
+        );
+    }
+}
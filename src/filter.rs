@@ -47,6 +47,7 @@ pub enum FilterOperator {
 
 impl FilterOperator {
     /// 从字符串解析操作符
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s {
             "==" | "=" => Ok(FilterOperator::Equal),
@@ -129,7 +130,7 @@ impl FilterCondition {
     /// 解析过滤条件字符串
     /// 格式: "column operator value" 或 "column operator" (对于 is_empty/is_not_empty)
     pub fn parse(condition_str: &str) -> Result<Self> {
-        let parts: Vec<&str> = condition_str.trim().split_whitespace().collect();
+        let parts: Vec<&str> = condition_str.split_whitespace().collect();
 
         if parts.len() < 2 {
             return Err(ExcelCliError::ExportError(format!(
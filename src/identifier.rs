@@ -0,0 +1,439 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::SqlDialect;
+
+/// 标识符大小写风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// snake_case：音译后按单词（含驼峰边界）拆分，小写并用下划线连接
+    Snake,
+    /// 小写，非标识符字符替换为下划线
+    Lower,
+    /// 大写，非标识符字符替换为下划线
+    Upper,
+    /// 保持原样，只应用重命名和长度限制
+    Keep,
+}
+
+impl IdentifierCase {
+    /// 从字符串解析大小写风格
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "snake" | "snake_case" | "snake-case" => Ok(IdentifierCase::Snake),
+            "lower" | "lowercase" => Ok(IdentifierCase::Lower),
+            "upper" | "uppercase" => Ok(IdentifierCase::Upper),
+            "keep" | "none" | "preserve" => Ok(IdentifierCase::Keep),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的标识符风格: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 解析 `原列名=新列名` 形式的重命名
+pub fn parse_rename(s: &str) -> Result<(String, String)> {
+    match s.rsplit_once('=') {
+        Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+            Ok((from.trim().to_string(), to.trim().to_string()))
+        }
+        _ => Err(ExcelCliError::UnsupportedFormat(format!(
+            "无效的列重命名 '{}'，应为 原列名=新列名",
+            s
+        ))),
+    }
+}
+
+// 保留字表以空白分隔，均为大写
+
+/// 各方言通用的保留字
+const COMMON_RESERVED: &str = "\
+    ALL ALTER AND ANY AS ASC BETWEEN BY CASE CHECK COLUMN CONSTRAINT CREATE CROSS DEFAULT \
+    DELETE DESC DISTINCT DROP ELSE END EXISTS FALSE FOR FOREIGN FROM FULL GRANT GROUP HAVING \
+    IN INNER INSERT INTERSECT INTO IS JOIN LEFT LIKE NOT NULL ON OR ORDER OUTER PRIMARY \
+    REFERENCES RIGHT SELECT SET TABLE THEN TO TRUE UNION UNIQUE UPDATE USING VALUES WHEN WHERE \
+    WITH";
+
+const MYSQL_RESERVED: &str = "\
+    CONDITION DATABASE DIV DUAL INDEX INTERVAL KEY KEYS LIMIT LOAD LOCK MATCH MOD OPTION RANGE \
+    RANK READ REGEXP RELEASE REPLACE RETURN RLIKE ROW ROWS SCHEMA SHOW SQL USAGE WRITE XOR";
+
+const POSTGRES_RESERVED: &str = "\
+    ANALYSE ANALYZE ARRAY ASYMMETRIC BOTH CAST COLLATE CURRENT_DATE CURRENT_TIME \
+    CURRENT_TIMESTAMP CURRENT_USER DEFERRABLE DO EXCEPT FETCH INITIALLY LATERAL LEADING LIMIT \
+    LOCALTIME LOCALTIMESTAMP OFFSET ONLY PLACING RETURNING SESSION_USER SOME SYMMETRIC \
+    TRAILING USER VARIADIC WINDOW";
+
+const ORACLE_RESERVED: &str = "\
+    ACCESS AUDIT CLUSTER COMMENT COMPRESS CONNECT CURRENT DATE DECIMAL EXCLUSIVE FILE FLOAT \
+    IDENTIFIED IMMEDIATE INCREMENT INDEX INITIAL INTEGER LEVEL LOCK LONG MAXEXTENTS MINUS MODE \
+    MODIFY NOAUDIT NOCOMPRESS NOWAIT NUMBER OF OFFLINE ONLINE OPTION PCTFREE PRIOR PRIVILEGES \
+    PUBLIC RAW RENAME RESOURCE REVOKE ROW ROWID ROWNUM ROWS SESSION SHARE SIZE SMALLINT START \
+    SUCCESSFUL SYNONYM SYSDATE UID USER VALIDATE VARCHAR VARCHAR2 VIEW WHENEVER";
+
+const SQLSERVER_RESERVED: &str = "\
+    BACKUP BREAK BROWSE BULK CASCADE CHECKPOINT CLOSE CLUSTERED COMMIT COMPUTE CONTAINS \
+    CONTINUE CURRENT CURSOR DATABASE DBCC DEALLOCATE DECLARE DENY DISK DOUBLE DUMP ESCAPE \
+    EXCEPT EXEC EXECUTE EXIT FETCH FILE FILLFACTOR FUNCTION GOTO HOLDLOCK IDENTITY IF INDEX \
+    KEY KILL LOAD MERGE NOCHECK NONCLUSTERED OF OFF OFFSETS OPEN OPTION OVER PERCENT PIVOT \
+    PLAN PRINT PROC PROCEDURE PUBLIC READ RESTORE RETURN REVERT REVOKE ROLLBACK ROWCOUNT RULE \
+    SAVE SCHEMA SHUTDOWN SOME STATISTICS TOP TRAN TRANSACTION TRIGGER TRUNCATE UNPIVOT USE \
+    USER VIEW WAITFOR WHILE";
+
+const WAREHOUSE_RESERVED: &str = "\
+    ARRAY EXCEPT ILIKE LATERAL LIMIT OFFSET QUALIFY SAMPLE STRUCT WINDOW";
+
+/// 判断标识符是否为方言的保留字（不区分大小写）
+pub fn is_reserved_word(dialect: SqlDialect, word: &str) -> bool {
+    let word = word.to_uppercase();
+    let extra = match dialect {
+        SqlDialect::MySQL => MYSQL_RESERVED,
+        SqlDialect::PostgreSQL => POSTGRES_RESERVED,
+        SqlDialect::Oracle => ORACLE_RESERVED,
+        SqlDialect::SqlServer => SQLSERVER_RESERVED,
+        SqlDialect::SQLite => "",
+        SqlDialect::ClickHouse
+        | SqlDialect::DuckDB
+        | SqlDialect::Snowflake
+        | SqlDialect::BigQuery => WAREHOUSE_RESERVED,
+    };
+    COMMON_RESERVED
+        .split_whitespace()
+        .chain(extra.split_whitespace())
+        .any(|reserved| reserved == word)
+}
+
+/// 方言的标识符最大长度（字节），None 表示不限制
+///
+/// Oracle 12.2 之前为 30，之后为 128；默认按 30 处理，可通过 `with_max_length` 放宽
+pub fn max_identifier_length(dialect: SqlDialect) -> Option<usize> {
+    match dialect {
+        SqlDialect::MySQL => Some(64),
+        SqlDialect::PostgreSQL => Some(63),
+        SqlDialect::SqlServer => Some(128),
+        SqlDialect::Oracle => Some(30),
+        SqlDialect::Snowflake => Some(255),
+        SqlDialect::BigQuery => Some(300),
+        SqlDialect::SQLite | SqlDialect::ClickHouse | SqlDialect::DuckDB => None,
+    }
+}
+
+/// 按非字母数字字符拆分单词，只保留 ASCII 字母和数字
+///
+/// `camel_case` 为 true 时还在驼峰边界处断开：orderDate、OrderID、HTTPServer
+pub(crate) fn split_words(name: &str, camel_case: bool) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if camel_case && c.is_ascii_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// 标识符规范化器：把 Excel 表头转换为适合目标方言的列名和表名
+#[derive(Debug, Clone)]
+pub struct IdentifierNormalizer {
+    dialect: SqlDialect,
+    case: IdentifierCase,
+    max_length: Option<usize>,
+    renames: Vec<(String, String)>,
+}
+
+impl IdentifierNormalizer {
+    /// 创建新的规范化器（默认保持原样，使用方言的长度限制）
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            dialect,
+            case: IdentifierCase::Keep,
+            max_length: max_identifier_length(dialect),
+            renames: Vec::new(),
+        }
+    }
+
+    /// 根据命令行选项创建规范化器，未设置任何相关选项时返回 None
+    pub fn from_options(
+        dialect: SqlDialect,
+        case: Option<&str>,
+        renames: &[String],
+        max_length: Option<usize>,
+    ) -> Result<Option<Self>> {
+        if case.is_none() && renames.is_empty() && max_length.is_none() {
+            return Ok(None);
+        }
+
+        let case = match case {
+            Some(case) => IdentifierCase::from_str(case)?,
+            None => IdentifierCase::Keep,
+        };
+        let renames = renames
+            .iter()
+            .map(|r| parse_rename(r))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut normalizer = Self::new(dialect).with_case(case).with_renames(renames);
+        if let Some(max_length) = max_length {
+            normalizer = normalizer.with_max_length(max_length);
+        }
+        Ok(Some(normalizer))
+    }
+
+    /// 设置大小写风格
+    pub fn with_case(mut self, case: IdentifierCase) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// 设置标识符最大长度（字节），覆盖方言默认值
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length.max(1));
+        self
+    }
+
+    /// 设置按名称的重命名（原列名 -> 新列名），新列名原样使用
+    pub fn with_renames(mut self, renames: Vec<(String, String)>) -> Self {
+        self.renames = renames;
+        self
+    }
+
+    /// 规范化表名（`schema.table` 按段分别处理）
+    pub fn normalize_table(&self, name: &str) -> String {
+        name.split('.')
+            .map(|part| {
+                let normalized = self.normalize_word(part);
+                if normalized.is_empty() {
+                    part.to_string()
+                } else {
+                    normalized
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// 规范化一组列名，保证结果互不重复（不区分大小写）
+    pub fn normalize_all(&self, headers: &[String]) -> Result<Vec<String>> {
+        let renames: HashMap<&str, &str> = self
+            .renames
+            .iter()
+            .map(|(from, to)| (from.as_str(), to.as_str()))
+            .collect();
+        for from in renames.keys() {
+            if !headers.iter().any(|h| h == from) {
+                return Err(ExcelCliError::ExportError(format!(
+                    "重命名的列 '{}' 不存在",
+                    from
+                )));
+            }
+        }
+
+        // 重命名的列名优先占用，其余列名遇到冲突时追加 _2、_3 ...
+        let mut used = HashSet::new();
+        for header in headers {
+            if let Some(to) = renames.get(header.as_str()) {
+                if !used.insert(to.to_lowercase()) {
+                    return Err(ExcelCliError::ExportError(format!(
+                        "重命名后的列名 '{}' 重复",
+                        to
+                    )));
+                }
+            }
+        }
+
+        let mut names = Vec::with_capacity(headers.len());
+        for (i, header) in headers.iter().enumerate() {
+            if let Some(to) = renames.get(header.as_str()) {
+                names.push(to.to_string());
+                continue;
+            }
+            let mut base = self.normalize_word(header);
+            if base.is_empty() {
+                base = self.apply_case(&format!("column_{}", i + 1));
+            }
+            let name = self.unique_name(&base, &used);
+            used.insert(name.to_lowercase());
+            names.push(name);
+        }
+        Ok(names)
+    }
+
+    /// 把生成的名称（如索引名）截断到最大长度，与 `used` 中已有的名称冲突时追加 _2、_3 ...
+    ///
+    /// 不转换风格，返回的名称会加入 `used`
+    pub(crate) fn fit_name(&self, name: &str, used: &mut HashSet<String>) -> String {
+        let name = self.unique_name(self.truncate(name), used);
+        used.insert(name.to_lowercase());
+        name
+    }
+
+    /// 规范化单个标识符：转换风格、规避保留字并截断
+    fn normalize_word(&self, name: &str) -> String {
+        if self.case == IdentifierCase::Keep {
+            return self.truncate(name.trim()).to_string();
+        }
+
+        let mut word = self.apply_case(&deunicode::deunicode(name));
+        if word.starts_with(|c: char| c.is_ascii_digit()) {
+            word.insert(0, '_');
+        }
+        if !word.is_empty() && is_reserved_word(self.dialect, &word) {
+            word.push('_');
+        }
+        self.truncate(&word).to_string()
+    }
+
+    /// 按大小写风格拆分单词并连接，只保留 ASCII 字母、数字和下划线
+    fn apply_case(&self, name: &str) -> String {
+        let joined = split_words(name, self.case == IdentifierCase::Snake).join("_");
+        match self.case {
+            IdentifierCase::Upper => joined.to_uppercase(),
+            _ => joined.to_lowercase(),
+        }
+    }
+
+    /// 截断到最大长度（按字节，不拆分字符）
+    fn truncate<'a>(&self, name: &'a str) -> &'a str {
+        let Some(max) = self.max_length else {
+            return name;
+        };
+        if name.len() <= max {
+            return name;
+        }
+        let mut end = max;
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        let truncated = &name[..end];
+        let trimmed = truncated.trim_end_matches('_');
+        if trimmed.is_empty() {
+            truncated
+        } else {
+            trimmed
+        }
+    }
+
+    /// 生成不与已有列名冲突的名称，追加后缀后仍满足长度限制
+    fn unique_name(&self, base: &str, used: &HashSet<String>) -> String {
+        if !used.contains(&base.to_lowercase()) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| {
+                let suffix = format!("_{}", n);
+                let room = self
+                    .max_length
+                    .map_or(base.len(), |max| max.saturating_sub(suffix.len()));
+                let mut end = room.min(base.len());
+                while !base.is_char_boundary(end) {
+                    end -= 1;
+                }
+                format!("{}{}", &base[..end], suffix)
+            })
+            .find(|candidate| !used.contains(&candidate.to_lowercase()))
+            .expect("后缀序号无穷，总能找到未使用的名称")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_snake_case_and_transliteration() {
+        let normalizer =
+            IdentifierNormalizer::new(SqlDialect::PostgreSQL).with_case(IdentifierCase::Snake);
+        let names = normalizer
+            .normalize_all(&headers(&[
+                "Order Date (UTC)",
+                "客户名称",
+                "OrderID",
+                "2024 Sales",
+                "user",
+                "",
+            ]))
+            .unwrap();
+        assert_eq!(
+            names,
+            vec![
+                "order_date_utc",
+                "ke_hu_ming_cheng",
+                "order_id",
+                "_2024_sales",
+                "user_",
+                "column_6"
+            ]
+        );
+
+        let upper = IdentifierNormalizer::new(SqlDialect::Oracle).with_case(IdentifierCase::Upper);
+        assert_eq!(
+            upper.normalize_table("sales.Order Items"),
+            "SALES.ORDER_ITEMS"
+        );
+        assert!(is_reserved_word(SqlDialect::Oracle, "date"));
+        assert!(!is_reserved_word(SqlDialect::PostgreSQL, "date"));
+    }
+
+    #[test]
+    fn test_truncation_and_collisions() {
+        let normalizer =
+            IdentifierNormalizer::new(SqlDialect::Oracle).with_case(IdentifierCase::Snake);
+        let long_a = "Quarterly Revenue Adjustment Amount North";
+        let long_b = "Quarterly Revenue Adjustment Amount South";
+        let names = normalizer
+            .normalize_all(&headers(&[long_a, long_b, "Name", "name"]))
+            .unwrap();
+        assert_eq!(names[0], "quarterly_revenue_adjustment_a");
+        assert_eq!(names[1], "quarterly_revenue_adjustment_2");
+        assert_eq!(names[2], "name");
+        assert_eq!(names[3], "name_2");
+        assert!(names.iter().all(|n| n.len() <= 30));
+
+        let wider = normalizer.clone().with_max_length(128);
+        assert_eq!(
+            wider.normalize_all(&headers(&[long_a])).unwrap()[0],
+            "quarterly_revenue_adjustment_amount_north"
+        );
+    }
+
+    #[test]
+    fn test_renames() {
+        let normalizer = IdentifierNormalizer::new(SqlDialect::MySQL)
+            .with_case(IdentifierCase::Snake)
+            .with_renames(vec![parse_rename("Order Date=order_date").unwrap()]);
+        let names = normalizer
+            .normalize_all(&headers(&["Order Date", "Order-Date"]))
+            .unwrap();
+        assert_eq!(names, vec!["order_date", "order_date_2"]);
+
+        let keep = IdentifierNormalizer::new(SqlDialect::MySQL)
+            .with_renames(vec![("Missing".to_string(), "x".to_string())]);
+        assert!(keep.normalize_all(&headers(&["Order Date"])).is_err());
+        assert!(parse_rename("no-target=").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::{ExporterConfig, ExporterFactory};
+use crate::filter::{DataFilter, FilterCondition};
+use crate::merge::{expand_inputs, ColumnAlignment, DataMerger};
+use crate::models::ExcelData;
+use crate::reader::ExcelReader;
+use crate::split::DataSplitter;
+
+/// 单个或多个字符串，任务文件中可以写 `input: a.xlsx` 或 `input: [a.xlsx, b.xlsx]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    /// 单个值
+    One(String),
+    /// 多个值
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    /// 转换为列表
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            OneOrMany::One(value) => vec![value.clone()],
+            OneOrMany::Many(values) => values.clone(),
+        }
+    }
+}
+
+/// 任务文件：包含多个转换任务
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobFile {
+    /// 转换任务
+    pub jobs: Vec<Job>,
+}
+
+/// 一个转换任务，对应一次 `convert`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Job {
+    /// 任务名称（默认为 `job-序号`）
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 输入文件路径，支持通配符
+    pub input: OneOrMany,
+    /// 工作表名称（默认使用第一个工作表）
+    #[serde(default)]
+    pub sheet: Option<String>,
+    /// 读取每个输入文件的所有工作表
+    #[serde(default)]
+    pub all_sheets: bool,
+    /// 把所有输入合并为一份数据输出（默认每个工作表单独输出）
+    #[serde(default)]
+    pub merge: bool,
+    /// 合并时的列对齐方式 (union, intersection)
+    #[serde(default)]
+    pub merge_columns: Option<String>,
+    /// 添加 `_source_file` 和 `_source_sheet` 列
+    #[serde(default)]
+    pub add_source: bool,
+    /// 选择的列
+    #[serde(default)]
+    pub select: Vec<String>,
+    /// 排除的列
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// 过滤条件，如 `Age > 30`
+    #[serde(default)]
+    pub filter: Vec<String>,
+    /// 输出格式
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// 输出路径模板，`{stem}` 为输入文件名（不含扩展名），`{sheet}` 为工作表名，
+    /// 拆分输出时还可以使用 `{n}` 和 `{拆分列名}`
+    pub output: String,
+    /// 按列值拆分
+    #[serde(default)]
+    pub split_by: Option<String>,
+    /// 每个文件的最大行数
+    #[serde(default)]
+    pub split_rows: Option<usize>,
+    /// 导出器选项，与 `convert` 的命令行选项对应
+    #[serde(default)]
+    pub options: ExporterConfig,
+}
+
+/// 任务执行结果
+#[derive(Debug, Clone)]
+pub struct JobReport {
+    /// 读取的工作表数量
+    pub sheets: usize,
+    /// 导出的行数
+    pub rows: usize,
+    /// 生成的文件
+    pub outputs: Vec<String>,
+}
+
+/// 任务及其执行结果、耗时
+#[derive(Debug)]
+pub struct JobOutcome {
+    /// 任务名称
+    pub name: String,
+    /// 执行结果
+    pub result: Result<JobReport>,
+    /// 耗时
+    pub elapsed: Duration,
+}
+
+fn default_format() -> String {
+    "json".to_string()
+}
+
+impl JobFile {
+    /// 读取任务文件，按扩展名识别 YAML（.yaml / .yml）或 TOML（.toml）
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let content = content.trim_start_matches('\u{FEFF}');
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let file: JobFile = match extension.as_str() {
+            "toml" => toml::from_str(content)
+                .map_err(|e| ExcelCliError::ExportError(format!("任务文件解析失败: {}", e)))?,
+            "yaml" | "yml" => serde_yaml::from_str(content)
+                .map_err(|e| ExcelCliError::ExportError(format!("任务文件解析失败: {}", e)))?,
+            _ => {
+                return Err(ExcelCliError::UnsupportedFormat(format!(
+                    "不支持的任务文件格式: {}",
+                    path.display()
+                )))
+            }
+        };
+        if file.jobs.is_empty() {
+            return Err(ExcelCliError::ExportError("任务文件中没有任务".to_string()));
+        }
+        Ok(file)
+    }
+
+    /// 任务名称，未命名的任务为 `job-序号`
+    pub fn job_name(&self, index: usize) -> String {
+        self.jobs[index]
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("job-{}", index + 1))
+    }
+
+    /// 用最多 `threads` 个线程并行执行所有任务，结果顺序与任务顺序一致
+    pub fn run(&self, threads: usize) -> Vec<JobOutcome> {
+        let next = AtomicUsize::new(0);
+        let outcomes: Mutex<Vec<Option<JobOutcome>>> =
+            Mutex::new((0..self.jobs.len()).map(|_| None).collect());
+
+        std::thread::scope(|scope| {
+            for _ in 0..threads.max(1).min(self.jobs.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(job) = self.jobs.get(index) else {
+                        break;
+                    };
+                    let started = Instant::now();
+                    let result = job.run();
+                    let outcome = JobOutcome {
+                        name: self.job_name(index),
+                        result,
+                        elapsed: started.elapsed(),
+                    };
+                    outcomes.lock().unwrap()[index] = Some(outcome);
+                });
+            }
+        });
+
+        outcomes
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+impl Job {
+    /// 执行任务：读取、合并、过滤后导出，返回生成的文件
+    pub fn run(&self) -> Result<JobReport> {
+        let split = self.split_by.is_some() || self.split_rows.is_some();
+        if split {
+            self.options.check_split(&self.format)?;
+        }
+        let filter = self.data_filter()?;
+        let inputs = expand_inputs(&self.input.to_vec())?;
+        if inputs.is_empty() {
+            return Err(ExcelCliError::ExportError("任务没有输入文件".to_string()));
+        }
+
+        let mut sources = Vec::new();
+        for input in &inputs {
+            let reader = ExcelReader::new(input);
+            let sheets = if self.all_sheets {
+                reader.get_sheet_names()?.into_iter().map(Some).collect()
+            } else {
+                vec![self.sheet.clone()]
+            };
+            for sheet in sheets {
+                sources.push((input.clone(), reader.read_sheet(sheet.as_deref())?));
+            }
+        }
+
+        let merger = DataMerger::new()
+            .with_alignment(match &self.merge_columns {
+                Some(alignment) => ColumnAlignment::from_str(alignment)?,
+                None => ColumnAlignment::Union,
+            })
+            .with_source_columns(self.add_source);
+        let units: Vec<(&str, ExcelData)> = if self.merge {
+            vec![(sources[0].0.as_str(), merger.merge(&sources)?)]
+        } else if self.add_source {
+            sources
+                .iter()
+                .map(|source| {
+                    Ok((
+                        source.0.as_str(),
+                        merger.merge(std::slice::from_ref(source))?,
+                    ))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            sources
+                .iter()
+                .map(|(input, data)| (input.as_str(), data.clone()))
+                .collect()
+        };
+
+        let exporter = ExporterFactory::create(&self.format, self.options.clone())?;
+        let mut splitter = DataSplitter::new();
+        if let Some(column) = &self.split_by {
+            splitter = splitter.with_column(column.clone());
+        }
+        if let Some(rows) = self.split_rows {
+            splitter = splitter.with_max_rows(rows);
+        }
+
+        // 先确定所有输出路径，避免多个输入写入同一文件
+        let mut outputs = Vec::new();
+        for (input, data) in units {
+            let data = match &filter {
+                Some(filter) => filter.apply(&data)?,
+                None => data,
+            };
+            let stem = Path::new(input)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let template = self
+                .output
+                .replace("{stem}", &stem)
+                .replace("{sheet}", &data.sheet_name);
+            if split {
+                let chunks = splitter.split(&data)?;
+                let paths = splitter.output_paths(&template, &chunks)?;
+                outputs.extend(chunks.into_iter().map(|c| c.data).zip(paths));
+            } else {
+                outputs.push((data, template));
+            }
+        }
+        let mut seen = HashSet::new();
+        if let Some((_, path)) = outputs.iter().find(|(_, path)| !seen.insert(path)) {
+            return Err(ExcelCliError::ExportError(format!(
+                "多个输入写入同一输出文件 '{}'，请在输出路径中使用 {{stem}} 或 {{sheet}}",
+                path
+            )));
+        }
+
+        let mut report = JobReport {
+            sheets: sources.len(),
+            rows: 0,
+            outputs: Vec::new(),
+        };
+        for (data, path) in outputs {
+            if let Some(parent) = Path::new(&path).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            exporter.export(&data, &path)?;
+            report.rows += data.row_count();
+            report.outputs.push(path);
+        }
+        Ok(report)
+    }
+
+    /// 根据 select / exclude / filter 创建过滤器，没有过滤时返回 None
+    fn data_filter(&self) -> Result<Option<DataFilter>> {
+        if self.select.is_empty() && self.exclude.is_empty() && self.filter.is_empty() {
+            return Ok(None);
+        }
+        let mut filter = DataFilter::new();
+        if !self.select.is_empty() {
+            filter = filter.with_select(self.select.clone());
+        }
+        if !self.exclude.is_empty() {
+            filter = filter.with_exclude(self.exclude.clone());
+        }
+        for condition in &self.filter {
+            filter = filter.with_condition(FilterCondition::parse(condition)?);
+        }
+        Ok(Some(filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_job_files() {
+        let yaml = r#"
+jobs:
+  - name: users
+    input: ["data/*.xlsx"]
+    sheet: users
+    filter: ["Age > 30"]
+    format: sql
+    output: "out/{stem}.sql"
+    options:
+      sql_dialect: postgresql
+      sql_table: users
+      primary_keys: [ID]
+  - input: regions/*.xlsx
+    merge: true
+    add_source: true
+    format: csv
+    output: out/regions-{n}.csv
+    split_rows: 50000
+"#;
+        let file: JobFile = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(file.jobs.len(), 2);
+        assert_eq!(file.job_name(0), "users");
+        assert_eq!(file.job_name(1), "job-2");
+        assert_eq!(
+            file.jobs[0].options.sql_dialect.as_deref(),
+            Some("postgresql")
+        );
+        assert_eq!(file.jobs[1].input.to_vec(), vec!["regions/*.xlsx"]);
+        assert_eq!(file.jobs[1].split_rows, Some(50000));
+
+        let toml = r#"
+[[jobs]]
+input = "a.xlsx"
+output = "a.json"
+
+[jobs.options]
+encoding = "gbk"
+"#;
+        let file: JobFile = toml::from_str(toml).unwrap();
+        assert_eq!(file.jobs[0].format, "json");
+        assert_eq!(file.jobs[0].options.encoding.as_deref(), Some("gbk"));
+
+        let unknown = "jobs:\n  - input: a.xlsx\n    output: a.json\n    optoins: {}\n";
+        assert!(serde_yaml::from_str::<JobFile>(unknown).is_err());
+    }
+
+    #[test]
+    fn test_run_reports_failures() {
+        let file: JobFile = serde_yaml::from_str(
+            "jobs:\n  - input: /nonexistent/*.xlsx\n    output: out.json\n  - input: a.xlsx\n    output: a.json\n    format: docx\n",
+        )
+        .unwrap();
+        let outcomes = file.run(4);
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| o.result.is_err()));
+        assert_eq!(outcomes[1].name, "job-2");
+
+        // 直接构造的空任务列表不启动线程
+        assert!(JobFile { jobs: Vec::new() }.run(4).is_empty());
+    }
+
+    #[test]
+    fn test_split_rejects_whole_table_sql() {
+        let file: JobFile = serde_yaml::from_str(
+            "jobs:\n  - input: a.xlsx\n    format: sql\n    output: out/{n}.sql\n    split_rows: 100\n    options:\n      sql_mode: sync\n      primary_keys: [ID]\n",
+        )
+        .unwrap();
+        let err = file.jobs[0].run().unwrap_err();
+        assert!(err.to_string().contains("Sync 模式需要完整的数据"));
+
+        let mut options = file.jobs[0].options.clone();
+        options.sql_mode = Some("delete".to_string());
+        assert!(options.check_split("sql").is_err());
+        options.sql_mode = Some("insert".to_string());
+        assert!(options.check_split("sql").is_ok());
+        options.sql_clear_table = Some("truncate".to_string());
+        assert!(options.check_split("sql").is_err());
+        assert!(options.check_split("csv").is_ok());
+    }
+}
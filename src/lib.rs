@@ -1,16 +1,39 @@
+pub mod config;
+pub mod data_diff;
+pub mod encoding;
 pub mod error;
 pub mod exporter;
 pub mod filter;
+pub mod identifier;
+pub mod job;
+pub mod merge;
+pub mod migration;
 pub mod models;
+pub mod profile;
 pub mod reader;
 pub mod schema;
+pub mod schema_diff;
+pub mod schema_target;
+pub mod split;
 
+pub use config::{ConfigProfile, ProjectConfig, CONFIG_FILE_NAME};
+pub use data_diff::{CellChange, DataDiff, RowDiff};
+pub use encoding::{OutputEncoding, TextEncoder, UnmappableAction};
 pub use error::{ExcelCliError, Result};
 pub use exporter::{
-    BuiltinTemplate, Exporter, ExporterConfig, ExporterFactory, SqlDialect, SqlExporter, SqlMode,
-    TemplateExporter,
+    load_snapshot, ArrowExporter, BuiltinTemplate, ClearTable, ColumnarCompression, CsvExporter,
+    CsvQuoteStyle, Exporter, ExporterConfig, ExporterFactory, LineTerminator, PreparedStatement,
+    SqlDialect, SqlExporter, SqlMode, SqlParam, TemplateExporter,
 };
 pub use filter::{DataFilter, FilterCondition};
+pub use identifier::{IdentifierCase, IdentifierNormalizer};
+pub use job::{Job, JobFile, JobOutcome, JobReport};
+pub use merge::{expand_inputs, ColumnAlignment, DataMerger};
+pub use migration::{MigrationFormat, MigrationWriter};
 pub use models::{CellValue, ExcelData, ExcelRow};
+pub use profile::{ColumnProfile, DataProfile, Profiler};
 pub use reader::ExcelReader;
-pub use schema::{SchemaGenerator, SqlType, TypeInference};
+pub use schema::{ColumnSchema, IndexSchema, SchemaGenerator, SqlType, TableSchema, TypeInference};
+pub use schema_diff::{parse_create_table, ColumnAlteration, SchemaChange, SchemaDiff};
+pub use schema_target::SchemaTarget;
+pub use split::{DataChunk, DataSplitter};
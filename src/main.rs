@@ -1,8 +1,11 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use comfy_table::{presets::UTF8_FULL, Cell, Color, ContentArrangement, Table};
 use excel_cli::{
-    DataFilter, ExcelReader, ExporterConfig, ExporterFactory, FilterCondition, Result,
-    SchemaGenerator, SqlDialect,
+    expand_inputs, parse_create_table, ColumnAlignment, ConfigProfile, DataDiff, DataFilter,
+    DataMerger, DataSplitter, ExcelCliError, ExcelData, ExcelReader, Exporter, ExporterConfig,
+    ExporterFactory, FilterCondition, IdentifierNormalizer, JobFile, MigrationFormat,
+    MigrationWriter, Profiler, ProjectConfig, Result, SchemaDiff, SchemaGenerator, SchemaTarget,
+    SqlDialect, SqlExporter, SqlMode, TableSchema, CONFIG_FILE_NAME,
 };
 use std::path::Path;
 
@@ -13,31 +16,41 @@ use std::path::Path;
 #[command(version = "0.3.0")]
 #[command(about = "将 Excel 文件转换为 JSON、CSV、SQL 等格式，支持数据过滤和 Schema 生成", long_about = None)]
 struct Cli {
+    /// 配置文件路径（默认使用当前目录中的 excel-cli.toml）
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<String>,
+
+    /// 使用配置文件中的命名配置，如 --profile warehouse
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
-    /// 转换 Excel 文件到指定格式
+    /// 转换 Excel 文件到指定格式，多个输入文件会先合并为一份数据
+    #[command(alias = "merge")]
     Convert {
-        /// Excel 文件路径
-        #[arg(short, long)]
-        input: String,
+        /// Excel 文件路径，可指定多个或使用通配符（如 "regions/*.xlsx"）
+        #[arg(short, long, required = true, num_args = 1..)]
+        input: Vec<String>,
 
-        /// 输出文件路径
+        /// 输出文件路径；拆分输出时为文件名模板，如 "out/{Region}.csv" 或 "out/part-{n}.sql"
         #[arg(short, long)]
         output: String,
 
-        /// 输出格式 (json, csv, sql, template, html, markdown, xml, yaml)
-        #[arg(short, long, default_value = "json")]
-        format: String,
+        /// 输出格式 (json, csv, tsv, sql, template, html, markdown, xml, yaml, parquet, arrow)，默认 json
+        #[arg(short, long)]
+        format: Option<String>,
 
         /// 工作表名称（可选，默认使用第一个工作表）
         #[arg(short, long)]
         sheet: Option<String>,
 
-        /// SQL 方言 (mysql, postgresql, sqlite, sqlserver, oracle) - 仅用于 SQL 格式
+        /// SQL 方言 (mysql, postgresql, sqlite, sqlserver, oracle, clickhouse, duckdb, snowflake, bigquery) - 仅用于 SQL 格式
         #[arg(long, value_name = "DIALECT")]
         sql_dialect: Option<String>,
 
@@ -45,9 +58,9 @@ enum Commands {
         #[arg(long, value_name = "TABLE")]
         sql_table: Option<String>,
 
-        /// SQL 语句模式 (insert, update, upsert) - 仅用于 SQL 格式
-        #[arg(long, value_name = "MODE", default_value = "insert")]
-        sql_mode: String,
+        /// SQL 语句模式 (insert, update, upsert, copy, delete, sync)，默认 insert - 仅用于 SQL 格式
+        #[arg(long, value_name = "MODE")]
+        sql_mode: Option<String>,
 
         /// 主键列（用逗号分隔，用于 UPDATE 和 UPSERT 模式）
         #[arg(long, value_name = "KEYS")]
@@ -61,6 +74,14 @@ enum Commands {
         #[arg(long, value_name = "COLUMNS")]
         column_mapping: Option<String>,
 
+        /// 上次同步时用 `-f json` 导出的快照文件 - 仅用于 SQL sync 模式
+        #[arg(long, value_name = "PATH")]
+        snapshot: Option<String>,
+
+        /// 创建索引的列，用逗号分隔的多列组成复合索引，可多次指定 - 仅用于 sqlite-db 格式
+        #[arg(long, value_name = "COLUMNS")]
+        index: Vec<String>,
+
         /// 自定义模板文件路径 - 仅用于 template 格式
         #[arg(long, value_name = "PATH")]
         template: Option<String>,
@@ -77,6 +98,41 @@ enum Commands {
         /// 支持多个条件，每个条件一个参数
         #[arg(long, value_name = "CONDITION")]
         filter: Vec<String>,
+
+        /// XML 单元格输出方式 (elements, attributes) - 仅用于 XML 格式
+        #[arg(long, value_name = "STYLE")]
+        xml_style: Option<String>,
+
+        /// 每个行组/批次的最大行数 - 仅用于 Parquet/Arrow 格式
+        #[arg(long, value_name = "ROWS")]
+        row_group_size: Option<usize>,
+
+        /// 压缩算法 (none, snappy, gzip, lz4, zstd) - 仅用于 Parquet/Arrow 格式
+        #[arg(long, value_name = "CODEC")]
+        compression: Option<String>,
+
+        /// 输出字符编码 (utf-8, gbk, gb18030, shift_jis, big5, utf-16 等)，默认 utf-8
+        #[arg(long, value_name = "ENCODING")]
+        encoding: Option<String>,
+
+        /// 遇到目标编码无法表示的字符时的处理方式 (fail, replace)，默认 fail
+        #[arg(long, value_name = "ACTION")]
+        encoding_errors: Option<String>,
+
+        #[command(flatten)]
+        csv: CsvArgs,
+
+        #[command(flatten)]
+        sql_load: SqlLoadArgs,
+
+        #[command(flatten)]
+        identifiers: IdentifierArgs,
+
+        #[command(flatten)]
+        merge: MergeArgs,
+
+        #[command(flatten)]
+        split: SplitArgs,
     },
 
     /// 列出 Excel 文件中的所有工作表
@@ -100,21 +156,37 @@ enum Commands {
         #[arg(short, long)]
         sheet: Option<String>,
 
-        /// SQL 方言
-        #[arg(long, value_name = "DIALECT", default_value = "mysql")]
-        sql_dialect: String,
+        /// SQL 方言，默认 mysql
+        #[arg(long, value_name = "DIALECT")]
+        sql_dialect: Option<String>,
 
-        /// SQL 表名
-        #[arg(long, value_name = "TABLE", default_value = "table_name")]
-        sql_table: String,
+        /// SQL 表名，默认 table_name
+        #[arg(long, value_name = "TABLE")]
+        sql_table: Option<String>,
 
-        /// 主键列名（可选）
-        #[arg(long, value_name = "COLUMN")]
+        /// 主键列名，用逗号分隔的多列组成复合主键（可选）
+        #[arg(long, value_name = "COLUMNS")]
         primary_key: Option<String>,
 
         /// 不添加 IF NOT EXISTS
         #[arg(long)]
         no_if_not_exists: bool,
+
+        /// 输出目标 (sql, jsonschema, avro, protobuf, typescript, rust-struct, pydantic)
+        #[arg(long, value_name = "TARGET", default_value = "sql")]
+        target: String,
+
+        #[command(flatten)]
+        constraints: SchemaConstraintArgs,
+
+        #[command(flatten)]
+        identifiers: IdentifierArgs,
+
+        #[command(flatten)]
+        diff: SchemaDiffArgs,
+
+        #[command(flatten)]
+        migration: MigrationArgs,
     },
 
     /// 显示支持的导出格式
@@ -134,11 +206,289 @@ enum Commands {
         #[arg(short, long)]
         limit: Option<usize>,
     },
+
+    /// 按主键对比两个 Excel 文件或两个工作表，列出新增、删除和修改的行
+    Diff {
+        /// 旧版 Excel 文件路径
+        #[arg(long, value_name = "FILE")]
+        old: String,
+
+        /// 新版 Excel 文件路径（可选，默认与旧版为同一文件，用于对比两个工作表）
+        #[arg(long, value_name = "FILE")]
+        new: Option<String>,
+
+        /// 旧版工作表名称（可选，默认使用第一个工作表）
+        #[arg(long, value_name = "SHEET")]
+        old_sheet: Option<String>,
+
+        /// 新版工作表名称（可选，默认使用第一个工作表）
+        #[arg(long, value_name = "SHEET")]
+        new_sheet: Option<String>,
+
+        /// 主键列，用逗号分隔的多列组成复合主键
+        #[arg(short, long, value_name = "COLUMNS")]
+        key: String,
+
+        /// 输出文件路径（可选，默认以表格输出到终端）
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// 输出格式 (table, json, csv, html, sql)，默认按输出文件扩展名判断
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// SQL 方言 - 仅用于 SQL 格式
+        #[arg(long, value_name = "DIALECT", default_value = "mysql")]
+        sql_dialect: String,
+
+        /// SQL 表名（默认使用新版工作表名）- 仅用于 SQL 格式
+        #[arg(long, value_name = "TABLE")]
+        sql_table: Option<String>,
+    },
+
+    /// 执行 YAML / TOML 任务文件中的多个转换任务
+    Run {
+        /// 任务文件路径（.yaml、.yml 或 .toml）
+        manifest: String,
+
+        /// 并行执行的任务数（默认为 CPU 核数）
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+    },
+
+    /// 统计每列的类型、空值、不同值、数值分布和高频值
+    #[command(alias = "stats")]
+    Profile {
+        /// Excel 文件路径
+        #[arg(short, long)]
+        input: String,
+
+        /// 工作表名称（可选，默认使用第一个工作表）
+        #[arg(short, long)]
+        sheet: Option<String>,
+
+        /// 输出文件路径（可选，默认以表格输出到终端）
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// 输出格式 (table, json, html)，默认按输出文件扩展名判断
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// 列出的高频值个数
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        top: usize,
+    },
+}
+
+/// CSV/TSV 格式选项
+#[derive(Args)]
+struct CsvArgs {
+    /// 分隔符，单个字符或 tab/comma/semicolon/pipe - 仅用于 CSV/TSV 格式
+    #[arg(long, value_name = "CHAR")]
+    delimiter: Option<String>,
+
+    /// 引号策略 (always, necessary, never, non-numeric) - 仅用于 CSV/TSV 格式
+    #[arg(long, value_name = "STYLE")]
+    quote_style: Option<String>,
+
+    /// 转义字符（默认通过双写引号转义） - 仅用于 CSV/TSV 格式
+    #[arg(long, value_name = "CHAR")]
+    escape_char: Option<String>,
+
+    /// 行结束符 (lf, crlf) - 仅用于 CSV/TSV 格式
+    #[arg(long, value_name = "TERMINATOR")]
+    line_terminator: Option<String>,
+
+    /// 不输出表头行 - 仅用于 CSV/TSV 格式
+    #[arg(long, overrides_with = "header")]
+    no_header: bool,
+
+    /// 输出表头行（覆盖配置文件中的 csv_no_header） - 仅用于 CSV/TSV 格式
+    #[arg(long, overrides_with = "no_header")]
+    header: bool,
+
+    /// 空值的输出文本（默认为空字符串） - 仅用于 CSV/TSV 格式
+    #[arg(long, value_name = "TEXT")]
+    null_as: Option<String>,
+
+    /// 在文件开头写入 UTF-8 BOM（Windows 版 Excel 正确显示中文需要） - 仅用于 CSV/TSV 格式
+    #[arg(long, overrides_with = "no_bom")]
+    bom: bool,
+
+    /// 不写入 BOM（覆盖配置文件中的 csv_bom） - 仅用于 CSV/TSV 格式
+    #[arg(long, overrides_with = "bom")]
+    no_bom: bool,
+}
+
+/// SQL 事务与加载选项
+#[derive(Args)]
+struct SqlLoadArgs {
+    /// 每条 INSERT/UPDATE/UPSERT/DELETE 语句合并的最大行数（默认 1000，1 表示逐行生成） - 仅用于 SQL 格式
+    #[arg(long, value_name = "N")]
+    batch_size: Option<usize>,
+
+    /// 用事务包裹所有语句 - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "no_transaction")]
+    transaction: bool,
+
+    /// 不使用事务（覆盖配置文件中的 sql_transaction） - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "transaction")]
+    no_transaction: bool,
+
+    /// 每 N 条语句提交一次（隐含 --transaction） - 仅用于 SQL 格式
+    #[arg(long, value_name = "N")]
+    commit_every: Option<usize>,
+
+    /// 写入出错即停止的前置语句（ON_ERROR_STOP、XACT_ABORT 等） - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "no_stop_on_error")]
+    stop_on_error: bool,
+
+    /// 不写入出错即停止的前置语句（覆盖配置文件中的 sql_stop_on_error） - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "stop_on_error")]
+    no_stop_on_error: bool,
+
+    /// 加载前清空目标表 (truncate, delete) - 仅用于 SQL 格式
+    #[arg(long, value_name = "MODE")]
+    clear_table: Option<String>,
+
+    /// 生成 SET IDENTITY_INSERT ON/OFF，允许写入标识列 - 仅用于 SQL Server
+    #[arg(long, overrides_with = "no_identity_insert")]
+    identity_insert: bool,
+
+    /// 不生成 SET IDENTITY_INSERT（覆盖配置文件中的 sql_identity_insert） - 仅用于 SQL Server
+    #[arg(long, overrides_with = "identity_insert")]
+    no_identity_insert: bool,
+
+    /// 按推断的列类型转换布尔、日期文本（yes → TRUE，2024.03.01 → 2024-03-01） - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "no_normalize_values")]
+    normalize_values: bool,
+
+    /// 保留单元格原文（覆盖配置文件中的 sql_normalize_values） - 仅用于 SQL 格式
+    #[arg(long, overrides_with = "normalize_values")]
+    no_normalize_values: bool,
+}
+
+/// Schema 类型推断、约束、索引和注释选项
+#[derive(Args)]
+struct SchemaConstraintArgs {
+    /// 创建普通索引的列，逗号分隔为复合索引，可多次指定
+    #[arg(long, value_name = "COLUMNS")]
+    index: Vec<String>,
+
+    /// 创建唯一索引的列，逗号分隔为复合索引，可多次指定
+    #[arg(long, value_name = "COLUMNS")]
+    unique: Vec<String>,
+
+    /// 不为没有空值的列推断 NOT NULL
+    #[arg(long)]
+    no_not_null: bool,
+
+    /// 类型推断容差：符合推断类型的非空值的最小比例，如 0.99（默认 1.0）
+    #[arg(long, value_name = "RATIO")]
+    type_tolerance: Option<f64>,
+
+    /// 第一行数据为列说明，生成列注释且不参与类型推断
+    #[arg(long)]
+    description_row: bool,
+
+    /// 表注释
+    #[arg(long, value_name = "TEXT")]
+    table_comment: Option<String>,
+}
+
+/// Schema 对比与保存选项
+#[derive(Args)]
+struct SchemaDiffArgs {
+    /// 与之前的表结构对比，生成 ALTER TABLE 语句；可以是旧版工作簿、--save-schema 保存的 JSON 或 CREATE TABLE 脚本
+    #[arg(long, value_name = "PATH")]
+    diff: Option<String>,
+
+    /// 输出可能丢失数据的类型收窄和删除列语句（默认以注释形式输出）
+    #[arg(long)]
+    allow_lossy: bool,
+
+    /// 把推断的表结构保存为 JSON，供以后 --diff 对比
+    #[arg(long, value_name = "PATH")]
+    save_schema: Option<String>,
+}
+
+/// 迁移文件选项
+#[derive(Args)]
+struct MigrationArgs {
+    /// 生成迁移文件而不是直接输出 SQL (diesel, sqlx, flyway, liquibase)
+    #[arg(long, value_name = "FORMAT")]
+    migration: Option<String>,
+
+    /// 迁移文件目录
+    #[arg(long, value_name = "DIR", default_value = "migrations")]
+    migration_dir: String,
+
+    /// Flyway 版本号（默认为目录中已有的最大版本号加 1）
+    #[arg(long, value_name = "N")]
+    migration_version: Option<u64>,
+}
+
+/// 标识符规范化选项
+#[derive(Args)]
+struct IdentifierArgs {
+    /// 列名和表名风格 (snake, lower, upper, keep)，音译非 ASCII 字符并规避保留字 - 仅用于 SQL 格式
+    #[arg(long, value_name = "CASE")]
+    identifier_case: Option<String>,
+
+    /// 按名称重命名列，形如 "Order Date=order_date"，可多次指定 - 仅用于 SQL 格式
+    #[arg(long, value_name = "FROM=TO")]
+    rename: Vec<String>,
+
+    /// 标识符最大长度，超出时截断并避免重名（默认按方言，如 Oracle 30、PostgreSQL 63） - 仅用于 SQL 格式
+    #[arg(long, value_name = "N")]
+    identifier_max_length: Option<usize>,
+}
+
+/// 多文件、多工作表合并选项
+#[derive(Args)]
+struct MergeArgs {
+    /// 读取每个输入文件的所有工作表并合并
+    #[arg(long)]
+    all_sheets: bool,
+
+    /// 合并时的列对齐方式 (union, intersection)
+    #[arg(long, value_name = "MODE", default_value = "union")]
+    merge_columns: String,
+
+    /// 添加 _source_file 和 _source_sheet 列，记录每行的来源
+    #[arg(long)]
+    add_source: bool,
+}
+
+/// 拆分输出选项
+#[derive(Args)]
+struct SplitArgs {
+    /// 按列值拆分，每个值输出一个文件，输出路径中用 {列名} 引用列值
+    #[arg(long, value_name = "COLUMN")]
+    split_by: Option<String>,
+
+    /// 每个文件的最大行数，输出路径中用 {n} 引用序号
+    #[arg(long, value_name = "N")]
+    split_rows: Option<usize>,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // 配置文件中的默认值，命令行参数优先
+    let defaults = match ProjectConfig::discover(cli.config.as_deref())? {
+        Some(config) => config.resolve(cli.profile.as_deref())?,
+        None if cli.profile.is_some() => {
+            eprintln!(
+                "❌ 错误: 使用 --profile 需要配置文件 {}（或通过 --config 指定）",
+                CONFIG_FILE_NAME
+            );
+            std::process::exit(1);
+        }
+        None => ConfigProfile::default(),
+    };
+
     match cli.command {
         Commands::Convert {
             input,
@@ -151,27 +501,66 @@ fn main() -> Result<()> {
             primary_keys,
             update_columns,
             column_mapping,
+            snapshot,
+            index,
             template,
             select,
             exclude,
             filter,
+            xml_style,
+            row_group_size,
+            compression,
+            encoding,
+            encoding_errors,
+            csv,
+            sql_load,
+            identifiers,
+            merge,
+            split,
         } => {
-            convert_excel(
+            let format = format
+                .or(defaults.format)
+                .unwrap_or_else(|| "json".to_string());
+            let filter = if filter.is_empty() {
+                defaults.filter.unwrap_or_default()
+            } else {
+                filter
+            };
+            let result = convert_excel(
                 &input,
                 &output,
                 &format,
-                sheet.as_deref(),
+                sheet.or(defaults.sheet).as_deref(),
                 sql_dialect,
                 sql_table,
-                &sql_mode,
+                sql_mode,
                 primary_keys,
                 update_columns,
                 column_mapping,
+                snapshot,
+                index,
                 template,
-                select,
-                exclude,
+                select.or(defaults.select.map(|cols| cols.join(","))),
+                exclude.or(defaults.exclude.map(|cols| cols.join(","))),
                 filter,
-            )?;
+                xml_style,
+                row_group_size,
+                compression,
+                encoding,
+                encoding_errors,
+                csv,
+                sql_load,
+                identifiers,
+                merge,
+                split,
+                defaults.options,
+            );
+            if let Err(ExcelCliError::EncodingError(_)) = &result {
+                eprintln!(
+                    "💡 提示: 可用 --encoding 选择其他编码，或使用 --encoding-errors replace 将无法表示的字符替换为 '?'"
+                );
+            }
+            result?;
         }
         Commands::ListSheets { input } => {
             list_sheets(&input)?;
@@ -184,15 +573,53 @@ fn main() -> Result<()> {
             sql_table,
             primary_key,
             no_if_not_exists,
+            target,
+            constraints,
+            identifiers,
+            diff,
+            migration,
         } => {
+            let options = defaults.options;
+            let sql_dialect = sql_dialect
+                .or(options.sql_dialect)
+                .unwrap_or_else(|| "mysql".to_string());
+            let sql_table = sql_table
+                .or(options.sql_table)
+                .unwrap_or_else(|| "table_name".to_string());
+            let identifiers = IdentifierArgs {
+                identifier_case: identifiers.identifier_case.or(options.identifier_case),
+                rename: if identifiers.rename.is_empty() {
+                    options.renames
+                } else {
+                    identifiers.rename
+                },
+                identifier_max_length: identifiers
+                    .identifier_max_length
+                    .or(options.identifier_max_length),
+            };
+            let indexes = if constraints.index.is_empty() {
+                options.indexes
+            } else {
+                constraints
+                    .index
+                    .iter()
+                    .map(|cols| cols.split(',').map(|s| s.trim().to_string()).collect())
+                    .collect()
+            };
             generate_schema(
                 &input,
                 output.as_deref(),
-                sheet.as_deref(),
+                sheet.or(defaults.sheet).as_deref(),
                 &sql_dialect,
                 &sql_table,
-                primary_key,
+                primary_key.or(options.primary_keys.map(|keys| keys.join(","))),
                 !no_if_not_exists,
+                &target,
+                constraints,
+                indexes,
+                identifiers,
+                diff,
+                migration,
             )?;
         }
         Commands::Formats => {
@@ -205,6 +632,47 @@ fn main() -> Result<()> {
         } => {
             preview_excel(&input, sheet.as_deref(), limit)?;
         }
+        Commands::Diff {
+            old,
+            new,
+            old_sheet,
+            new_sheet,
+            key,
+            output,
+            format,
+            sql_dialect,
+            sql_table,
+        } => {
+            diff_excel(
+                &old,
+                new.as_deref(),
+                old_sheet.as_deref(),
+                new_sheet.as_deref(),
+                &key,
+                output.as_deref(),
+                format.as_deref(),
+                &sql_dialect,
+                sql_table,
+            )?;
+        }
+        Commands::Run { manifest, jobs } => {
+            run_jobs(&manifest, jobs)?;
+        }
+        Commands::Profile {
+            input,
+            sheet,
+            output,
+            format,
+            top,
+        } => {
+            profile_excel(
+                &input,
+                sheet.as_deref(),
+                output.as_deref(),
+                format.as_deref(),
+                top,
+            )?;
+        }
     }
 
     Ok(())
@@ -213,41 +681,104 @@ fn main() -> Result<()> {
 /// 转换 Excel 文件
 #[allow(clippy::too_many_arguments)]
 fn convert_excel(
-    input: &str,
+    inputs: &[String],
     output: &str,
     format: &str,
     sheet_name: Option<&str>,
     sql_dialect: Option<String>,
     sql_table: Option<String>,
-    sql_mode: &str,
+    sql_mode: Option<String>,
     primary_keys: Option<String>,
     update_columns: Option<String>,
     column_mapping: Option<String>,
+    snapshot: Option<String>,
+    indexes: Vec<String>,
     template_path: Option<String>,
     select_columns: Option<String>,
     exclude_columns: Option<String>,
     filter_conditions: Vec<String>,
+    xml_style: Option<String>,
+    row_group_size: Option<usize>,
+    compression: Option<String>,
+    encoding: Option<String>,
+    encoding_errors: Option<String>,
+    csv: CsvArgs,
+    sql_load: SqlLoadArgs,
+    identifiers: IdentifierArgs,
+    merge: MergeArgs,
+    split: SplitArgs,
+    defaults: ExporterConfig,
 ) -> Result<()> {
+    let inputs = expand_inputs(inputs)?;
+
     // 检查输入文件是否存在
-    if !Path::new(input).exists() {
-        eprintln!("❌ 错误: 输入文件不存在: {}", input);
-        std::process::exit(1);
+    for input in &inputs {
+        if !Path::new(input).exists() {
+            eprintln!("❌ 错误: 输入文件不存在: {}", input);
+            std::process::exit(1);
+        }
     }
 
-    println!("📖 正在读取 Excel 文件: {}", input);
+    let mut sources = Vec::new();
+    for input in &inputs {
+        println!("📖 正在读取 Excel 文件: {}", input);
 
-    // 创建 Excel 读取器
-    let reader = ExcelReader::new(input);
+        // 创建 Excel 读取器
+        let reader = ExcelReader::new(input);
 
-    // 读取工作表数据
-    let mut data = reader.read_sheet(sheet_name)?;
+        // 读取工作表数据
+        let sheets = if merge.all_sheets {
+            reader.get_sheet_names()?.into_iter().map(Some).collect()
+        } else {
+            vec![sheet_name.map(String::from)]
+        };
+        for sheet in sheets {
+            let data = reader.read_sheet(sheet.as_deref())?;
+            println!(
+                "✅ 成功读取工作表 '{}': {} 行 × {} 列",
+                data.sheet_name,
+                data.row_count(),
+                data.column_count()
+            );
+            sources.push((input.clone(), data));
+        }
+    }
 
-    println!(
-        "✅ 成功读取工作表 '{}': {} 行 × {} 列",
-        data.sheet_name,
-        data.row_count(),
-        data.column_count()
-    );
+    // 多个文件或工作表按表头合并为一份数据；sqlite-db 每个工作表写入各自的表，
+    // 只合并不同文件中的同名工作表
+    let groups = if matches!(format.to_lowercase().as_str(), "sqlite-db" | "sqlite_db") {
+        let mut groups: Vec<Vec<(String, ExcelData)>> = Vec::new();
+        for source in sources {
+            match groups
+                .iter_mut()
+                .find(|group| group[0].1.sheet_name == source.1.sheet_name)
+            {
+                Some(group) => group.push(source),
+                None => groups.push(vec![source]),
+            }
+        }
+        groups
+    } else {
+        vec![sources]
+    };
+    let mut tables = Vec::new();
+    for mut sources in groups {
+        tables.push(if sources.len() == 1 && !merge.add_source {
+            sources.remove(0).1
+        } else {
+            let data = DataMerger::new()
+                .with_alignment(ColumnAlignment::from_str(&merge.merge_columns)?)
+                .with_source_columns(merge.add_source)
+                .merge(&sources)?;
+            println!(
+                "🔗 已合并 {} 个工作表: {} 行 × {} 列",
+                sources.len(),
+                data.row_count(),
+                data.column_count()
+            );
+            data
+        });
+    }
 
     // 应用数据过滤
     let has_filter =
@@ -286,29 +817,64 @@ fn convert_excel(
         }
 
         // 应用过滤
-        data = filter.apply(&data)?;
-        println!(
-            "✅ 过滤完成: {} 行 × {} 列",
-            data.row_count(),
-            data.column_count()
-        );
+        for data in &mut tables {
+            *data = filter.apply(data)?;
+            println!(
+                "✅ 过滤完成: {} 行 × {} 列",
+                data.row_count(),
+                data.column_count()
+            );
 
-        if data.row_count() == 0 {
-            println!("⚠️  警告: 过滤后没有数据行");
+            if data.row_count() == 0 {
+                println!("⚠️  警告: 过滤后没有数据行");
+            }
         }
     }
 
-    // 解析列名映射
-    let column_mapping_vec = column_mapping.map(|mapping| {
-        mapping
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect::<Vec<_>>()
-    });
+    // 解析列名映射、主键和更新列
+    let split_list =
+        |value: String| -> Vec<String> { value.split(',').map(|s| s.trim().to_string()).collect() };
+
+    // 创建导出器配置，未指定的选项使用配置文件中的默认值
+    let config = ExporterConfig {
+        sql_dialect,
+        sql_table,
+        column_mapping: column_mapping.map(split_list),
+        sql_mode,
+        primary_keys: primary_keys.map(split_list),
+        update_columns: update_columns.map(split_list),
+        snapshot,
+        indexes: indexes.into_iter().map(split_list).collect(),
+        template_path,
+        csv_delimiter: csv.delimiter,
+        csv_quote_style: csv.quote_style,
+        csv_escape: csv.escape_char,
+        csv_line_terminator: csv.line_terminator,
+        csv_no_header: switch(csv.no_header, csv.header),
+        csv_null_as: csv.null_as,
+        csv_bom: switch(csv.bom, csv.no_bom),
+        sql_batch_size: sql_load.batch_size,
+        sql_transaction: switch(sql_load.transaction, sql_load.no_transaction),
+        sql_commit_every: sql_load.commit_every,
+        sql_stop_on_error: switch(sql_load.stop_on_error, sql_load.no_stop_on_error),
+        sql_clear_table: sql_load.clear_table,
+        sql_identity_insert: switch(sql_load.identity_insert, sql_load.no_identity_insert),
+        sql_normalize_values: switch(sql_load.normalize_values, sql_load.no_normalize_values),
+        identifier_case: identifiers.identifier_case,
+        renames: identifiers.rename,
+        identifier_max_length: identifiers.identifier_max_length,
+        xml_style,
+        row_group_size,
+        compression,
+        encoding,
+        encoding_errors,
+    }
+    .with_defaults(defaults);
 
     // 如果是 SQL 格式且提供了列名映射，验证数量
     if format.to_lowercase() == "sql" {
-        if let Some(ref mapping) = column_mapping_vec {
+        let data = &tables[0];
+        if let Some(ref mapping) = config.column_mapping {
             if mapping.len() != data.column_count() {
                 eprintln!(
                     "❌ 错误: 列名映射数量({})与 Excel 列数({})不匹配",
@@ -326,58 +892,104 @@ fn convert_excel(
         }
 
         // 显示 SQL 配置
-        if let Some(ref dialect) = sql_dialect {
-            println!("🗄️  SQL 方言: {}", dialect);
-        }
-        // 显示 SQL 配置
-        if let Some(ref dialect) = sql_dialect {
+        if let Some(ref dialect) = config.sql_dialect {
             println!("🗄️  SQL 方言: {}", dialect);
         }
-        if let Some(ref table) = sql_table {
+        if let Some(ref table) = config.sql_table {
             println!("📊 表名: {}", table);
         }
-        println!("📌 SQL 模式: {}", sql_mode);
-        if let Some(ref keys) = primary_keys {
-            println!("🔑 主键列: {}", keys);
+        println!(
+            "📌 SQL 模式: {}",
+            config.sql_mode.as_deref().unwrap_or("insert")
+        );
+        if let Some(ref keys) = config.primary_keys {
+            println!("🔑 主键列: {}", keys.join(","));
+        }
+        if let Some(ref cols) = config.update_columns {
+            println!("✏️  更新列: {}", cols.join(","));
         }
-        if let Some(ref cols) = update_columns {
-            println!("✏️  更新列: {}", cols);
+        if let Some(ref path) = config.snapshot {
+            println!("📸 同步快照: {}", path);
         }
     }
 
-    // 解析主键和更新列
-    let primary_keys_vec = primary_keys.map(|keys| {
-        keys.split(',')
-            .map(|s| s.trim().to_string())
-            .collect::<Vec<_>>()
-    });
-
-    let update_columns_vec = update_columns.map(|cols| {
-        cols.split(',')
-            .map(|s| s.trim().to_string())
-            .collect::<Vec<_>>()
-    });
-
-    // 创建导出器配置
-    let config = ExporterConfig {
-        sql_dialect,
-        sql_table,
-        column_mapping: column_mapping_vec,
-        sql_mode: Some(sql_mode.to_string()),
-        primary_keys: primary_keys_vec,
-        update_columns: update_columns_vec,
-        template_path,
-    };
+    let split_output = split.split_by.is_some() || split.split_rows.is_some();
+    if split_output {
+        config.check_split(format)?;
+    }
+    if tables.len() > 1 {
+        if split_output {
+            return Err(ExcelCliError::ExportError(
+                "多个工作表分别写入各自的表时不能拆分输出".to_string(),
+            ));
+        }
+        if config.sql_table.is_some() {
+            return Err(ExcelCliError::ExportError(
+                "多个工作表分别写入以工作表名命名的表，不能指定 --sql-table".to_string(),
+            ));
+        }
+        // 主键和索引只用于包含这些列的表，但至少要有一个工作表包含
+        for columns in config.primary_keys.iter().chain(&config.indexes) {
+            if !tables
+                .iter()
+                .any(|data| columns.iter().all(|c| data.headers.contains(c)))
+            {
+                return Err(ExcelCliError::ExportError(format!(
+                    "没有工作表包含列: {}",
+                    columns.join(", ")
+                )));
+            }
+        }
+    }
 
     // 创建导出器
-    let exporter = ExporterFactory::create(format, config)?;
+    let exporter = ExporterFactory::create(format, config.clone())?;
 
     println!("📝 正在导出为 {} 格式...", exporter.format_name());
 
-    // 导出数据
-    exporter.export(&data, output)?;
+    if !split_output {
+        // 导出数据，多个工作表依次写入同一数据库文件
+        if let [data] = tables.as_slice() {
+            exporter.export(data, output)?;
+        } else {
+            for data in &tables {
+                let has_columns =
+                    |columns: &Vec<String>| columns.iter().all(|c| data.headers.contains(c));
+                let mut config = config.clone();
+                config.primary_keys = config.primary_keys.filter(has_columns);
+                config.indexes.retain(has_columns);
+                ExporterFactory::create(format, config)?.export(data, output)?;
+                println!("   📄 表 '{}' ({} 行)", data.sheet_name, data.row_count());
+            }
+        }
 
-    println!("✅ 转换完成! 输出文件: {}", output);
+        println!("✅ 转换完成! 输出文件: {}", output);
+        return Ok(());
+    }
+
+    // 拆分为多份数据，按文件名模板分别导出
+    let mut splitter = DataSplitter::new();
+    if let Some(column) = split.split_by {
+        splitter = splitter.with_column(column);
+    }
+    if let Some(rows) = split.split_rows {
+        splitter = splitter.with_max_rows(rows);
+    }
+    let chunks = splitter.split(&tables[0])?;
+    if chunks.is_empty() {
+        println!("⚠️  警告: 没有数据行，未生成文件");
+        return Ok(());
+    }
+    let paths = splitter.output_paths(output, &chunks)?;
+    for (chunk, path) in chunks.iter().zip(&paths) {
+        if let Some(parent) = Path::new(path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        exporter.export(&chunk.data, path)?;
+        println!("   📄 {} ({} 行)", path, chunk.data.row_count());
+    }
+
+    println!("✅ 转换完成! 共生成 {} 个文件", paths.len());
 
     Ok(())
 }
@@ -406,26 +1018,51 @@ fn show_formats() {
     for format in ExporterFactory::supported_formats() {
         println!("  • {}", format);
     }
+    println!("\n💡 CSV/TSV 选项:");
+    println!("  • --delimiter - 分隔符 (tsv 格式默认为制表符)");
+    println!("  • --quote-style - 引号策略 (always, necessary, never, non-numeric)");
+    println!("  • --escape-char / --line-terminator (lf, crlf)");
+    println!("  • --no-header / --null-as <TEXT> / --bom");
+    println!("\n💡 输出编码 (--encoding):");
+    println!("  • utf-8 (默认) / utf-16 / utf-16be");
+    println!("  • gbk / gb18030 / shift_jis / euc-jp / big5 / euc-kr 等");
+    println!("  • --encoding-errors replace - 将无法表示的字符替换为 '?'");
     println!("\n💡 SQL 格式支持的方言:");
     println!("  • mysql / mariadb");
     println!("  • postgresql / postgres / pg");
     println!("  • sqlite / sqlite3");
     println!("  • sqlserver / mssql / tsql");
     println!("  • oracle");
+    println!("  • clickhouse / ch");
+    println!("  • duckdb");
+    println!("  • snowflake");
+    println!("  • bigquery / bq");
     println!("\n💡 SQL 模式:");
     println!("  • insert (默认) - 生成 INSERT 语句");
     println!("  • update - 生成 UPDATE 语句");
     println!("  • upsert - 生成 UPSERT/MERGE 语句");
+    println!("  • copy - 生成 PostgreSQL COPY / MySQL LOAD DATA 批量加载（仅 PostgreSQL、MySQL）");
+    println!("  • delete - 按主键生成 DELETE 语句");
+    println!("  • sync - 与快照 (--snapshot) 对比，生成最少的 INSERT/UPDATE/DELETE");
+    println!("\n💡 XML / YAML:");
+    println!("  • xml - XML 格式，表头自动转换为合法元素名 (--xml-style elements|attributes)");
+    println!("  • yaml / yml - YAML 格式，字符串按需加引号转义");
+    println!("\n💡 数据库文件:");
+    println!("  • sqlite-db - SQLite 数据库文件，每个工作表一张表 (--sql-table, --primary-keys, --index)");
+    println!("\n💡 列式格式:");
+    println!(
+        "  • parquet - Apache Parquet (--compression snappy|gzip|lz4|zstd|none, --row-group-size)"
+    );
+    println!("  • arrow / ipc / feather - Arrow IPC 文件 (--compression lz4|zstd|none)");
     println!("\n💡 模板格式:");
     println!("  • html / html-table - HTML 表格");
     println!("  • markdown / md / md-table - Markdown 表格");
-    println!("  • xml - XML 格式");
-    println!("  • yaml / yml - YAML 格式");
     println!("  • template - 自定义 Tera 模板 (需配合 --template 参数)");
     println!("\n💡 提示: 可以通过实现 Exporter trait 添加更多格式支持");
 }
 
 /// 生成 CREATE TABLE 语句
+#[allow(clippy::too_many_arguments)]
 fn generate_schema(
     input: &str,
     output: Option<&str>,
@@ -434,6 +1071,12 @@ fn generate_schema(
     sql_table: &str,
     primary_key: Option<String>,
     add_if_not_exists: bool,
+    target: &str,
+    constraints: SchemaConstraintArgs,
+    indexes: Vec<Vec<String>>,
+    identifiers: IdentifierArgs,
+    diff: SchemaDiffArgs,
+    migration: MigrationArgs,
 ) -> Result<()> {
     // 检查输入文件是否存在
     if !Path::new(input).exists() {
@@ -463,9 +1106,13 @@ fn generate_schema(
         "sqlite" | "sqlite3" => SqlDialect::SQLite,
         "sqlserver" | "mssql" | "tsql" => SqlDialect::SqlServer,
         "oracle" => SqlDialect::Oracle,
+        "clickhouse" | "ch" => SqlDialect::ClickHouse,
+        "duckdb" => SqlDialect::DuckDB,
+        "snowflake" => SqlDialect::Snowflake,
+        "bigquery" | "bq" => SqlDialect::BigQuery,
         _ => {
             eprintln!("❌ 错误: 不支持的 SQL 方言: {}", sql_dialect);
-            eprintln!("支持的方言: mysql, postgresql, sqlite, sqlserver, oracle");
+            eprintln!("支持的方言: mysql, postgresql, sqlite, sqlserver, oracle, clickhouse, duckdb, snowflake, bigquery");
             std::process::exit(1);
         }
     };
@@ -480,13 +1127,160 @@ fn generate_schema(
     // 设置主键（如果指定）
     let generator = if let Some(pk) = primary_key {
         println!("🔑 主键: {}", pk);
-        generator.with_primary_key(pk)
+        generator.with_primary_keys(pk.split(',').map(|s| s.trim().to_string()).collect())
     } else {
         generator
     };
 
-    // 生成 CREATE TABLE 语句
-    let sql = generator.generate(&data)?;
+    // 设置索引、NOT NULL 推断和注释
+    let mut generator = generator
+        .with_not_null_inference(!constraints.no_not_null)
+        .with_description_row(constraints.description_row);
+    for cols in indexes {
+        generator = generator.with_index(cols);
+    }
+    for cols in &constraints.unique {
+        generator = generator.with_unique(cols.split(',').map(|s| s.trim().to_string()).collect());
+    }
+    if let Some(comment) = constraints.table_comment {
+        generator = generator.with_table_comment(comment);
+    }
+    if let Some(tolerance) = constraints.type_tolerance {
+        generator = generator.with_type_tolerance(tolerance);
+    }
+
+    // 设置标识符规范化（如果指定）
+    let generator = match IdentifierNormalizer::from_options(
+        dialect,
+        identifiers.identifier_case.as_deref(),
+        &identifiers.rename,
+        identifiers.identifier_max_length,
+    )? {
+        Some(normalizer) => generator.with_normalizer(normalizer),
+        None => generator,
+    };
+
+    // 推断表结构并生成 CREATE TABLE、注释和 CREATE INDEX 语句
+    let schema = generator.infer(&data)?;
+    let candidates: Vec<&str> = schema
+        .columns
+        .iter()
+        .filter(|c| c.unique_candidate)
+        .map(|c| c.name.as_str())
+        .collect();
+    if !candidates.is_empty() {
+        println!(
+            "💡 候选唯一列（可用 --unique 创建唯一索引）: {}",
+            candidates.join(", ")
+        );
+    }
+    for column in schema.columns.iter().filter(|c| !c.outliers.is_empty()) {
+        let samples: Vec<String> = column
+            .outliers
+            .iter()
+            .take(5)
+            .map(|(row, value)| format!("第 {} 行 '{}'", row, value))
+            .collect();
+        println!(
+            "⚠️  列 '{}' 推断为 {}，{} 个值不符合: {}",
+            column.name,
+            column.sql_type.to_sql_string(&dialect),
+            column.outliers.len(),
+            samples.join(", ")
+        );
+    }
+
+    if let Some(path) = &diff.save_schema {
+        std::fs::write(path, schema.to_json()?)?;
+        println!("💾 表结构已保存: {}", path);
+    }
+
+    // 非 SQL 目标：JSON Schema、Avro、Protobuf 和各语言的类型定义
+    let target = match target.to_lowercase().as_str() {
+        "sql" => None,
+        other => Some(SchemaTarget::from_str(other)?),
+    };
+    // 迁移文件：up 为建表和索引语句，down 为对应的 DROP TABLE
+    if let Some(format) = &migration.migration {
+        if diff.diff.is_some() || target.is_some() {
+            eprintln!("❌ 错误: --migration 不能与 --diff 或 --target 同时使用");
+            std::process::exit(1);
+        }
+        let mut up = vec![generator.create_table_sql(&schema)];
+        up.extend(generator.create_index_sqls(&schema));
+        let mut writer =
+            MigrationWriter::new(MigrationFormat::from_str(format)?, &migration.migration_dir);
+        if let Some(version) = migration.migration_version {
+            writer = writer.with_version(version);
+        }
+        let table = schema.name.rsplit('.').next().unwrap_or(&schema.name);
+        let paths = writer.write(
+            &format!("create_{}", table),
+            &up.join("\n"),
+            &generator.drop_table_sql(&schema),
+        )?;
+        for path in paths {
+            println!("✅ 迁移文件已生成: {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let sql = match (&diff.diff, target) {
+        (Some(_), Some(_)) => {
+            eprintln!("❌ 错误: --diff 只能生成 SQL，不能与 --target 同时使用");
+            std::process::exit(1);
+        }
+        (None, Some(target)) => target.render(&schema)?,
+        (Some(previous), None) => {
+            // 读取之前的表结构：schema JSON、CREATE TABLE 脚本或旧版工作簿
+            println!("🔍 对比之前的表结构: {}", previous);
+            let extension = Path::new(previous)
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let old_schema = match extension.as_str() {
+                "json" => TableSchema::from_json(&std::fs::read_to_string(previous)?)?,
+                "sql" | "ddl" => parse_create_table(&std::fs::read_to_string(previous)?)?,
+                _ => generator.infer(&ExcelReader::new(previous).read_sheet(sheet_name)?)?,
+            };
+
+            let schema_diff = SchemaDiff::compare(&old_schema, &schema, dialect);
+            if schema_diff.is_empty() {
+                println!("✅ 表结构没有变化");
+                return Ok(());
+            }
+            for alteration in schema_diff.lossy_changes() {
+                println!(
+                    "⚠️  列 '{}' 从 {} 收窄为 {}，可能丢失数据{}",
+                    alteration.name,
+                    alteration.from.to_sql_string(&dialect),
+                    alteration.to.to_sql_string(&dialect),
+                    if diff.allow_lossy {
+                        ""
+                    } else {
+                        "，语句已注释（--allow-lossy 输出）"
+                    }
+                );
+            }
+            for name in schema_diff.dropped_columns() {
+                println!(
+                    "⚠️  列 '{}' 在新数据中不存在，删除会丢失数据{}",
+                    name,
+                    if diff.allow_lossy {
+                        ""
+                    } else {
+                        "，语句已注释（--allow-lossy 输出）"
+                    }
+                );
+            }
+            schema_diff.alter_sqls(diff.allow_lossy).join("\n")
+        }
+        (None, None) => {
+            let mut statements = vec![generator.create_table_sql(&schema)];
+            statements.extend(generator.create_index_sqls(&schema));
+            statements.join("\n")
+        }
+    };
 
     // 输出结果
     match output {
@@ -497,7 +1291,13 @@ fn generate_schema(
         }
         None => {
             // 输出到标准输出
-            println!("\n📝 生成的 CREATE TABLE 语句:\n");
+            if let Some(target) = target {
+                println!("\n📝 生成的 {:?} 定义:\n", target);
+            } else if diff.diff.is_some() {
+                println!("\n📝 生成的 ALTER TABLE 语句:\n");
+            } else {
+                println!("\n📝 生成的 CREATE TABLE 语句:\n");
+            }
             println!("{}", sql);
         }
     }
@@ -579,3 +1379,371 @@ fn preview_excel(input: &str, sheet_name: Option<&str>, limit: Option<usize>) ->
 
     Ok(())
 }
+
+/// 按主键对比两份 Excel 数据
+#[allow(clippy::too_many_arguments)]
+fn diff_excel(
+    old_path: &str,
+    new_path: Option<&str>,
+    old_sheet: Option<&str>,
+    new_sheet: Option<&str>,
+    key: &str,
+    output: Option<&str>,
+    format: Option<&str>,
+    sql_dialect: &str,
+    sql_table: Option<String>,
+) -> Result<()> {
+    let new_path = new_path.unwrap_or(old_path);
+    for path in [old_path, new_path] {
+        if !Path::new(path).exists() {
+            eprintln!("❌ 错误: 输入文件不存在: {}", path);
+            std::process::exit(1);
+        }
+    }
+
+    let old = ExcelReader::new(old_path).read_sheet(old_sheet)?;
+    let new = ExcelReader::new(new_path).read_sheet(new_sheet)?;
+    println!(
+        "📖 旧版: {} [{}] {} 行，新版: {} [{}] {} 行",
+        old_path,
+        old.sheet_name,
+        old.row_count(),
+        new_path,
+        new.sheet_name,
+        new.row_count()
+    );
+
+    let keys: Vec<String> = key.split(',').map(|s| s.trim().to_string()).collect();
+    let diff = DataDiff::compare(&old, &new, &keys)?;
+    println!(
+        "🔍 新增 {} 行，删除 {} 行，修改 {} 行",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len()
+    );
+    if !diff.added_columns.is_empty() {
+        println!("   新增列: {}", diff.added_columns.join(", "));
+    }
+    if !diff.removed_columns.is_empty() {
+        println!("   删除列: {}", diff.removed_columns.join(", "));
+    }
+
+    let format = report_format(format, output);
+
+    if format == "sql" {
+        let Some(path) = output else {
+            eprintln!("❌ 错误: SQL 格式需要指定输出文件 (-o)");
+            std::process::exit(1);
+        };
+        // 按同一份对比结果生成语句，只写入两边都有的列
+        let mut data = new.clone();
+        data.headers = diff.columns.clone();
+        let table = sql_table.unwrap_or_else(|| new.sheet_name.clone());
+        SqlExporter::new(SqlDialect::from_str(sql_dialect)?, table)
+            .with_mode(SqlMode::Sync)
+            .with_primary_keys(keys)
+            .with_diff(diff)
+            .export(&data, path)?;
+        println!("✅ 变更 SQL 已生成! 输出文件: {}", path);
+        return Ok(());
+    }
+
+    let rendered = match format.as_str() {
+        "json" => diff.to_json()?,
+        "csv" => diff.to_csv()?,
+        "html" | "htm" => diff.to_html()?,
+        "table" => {
+            if diff.is_empty() {
+                println!("✅ 两份数据没有差异");
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(
+                ["变更", &diff.key_columns.join(", "), "列", "原值", "新值"]
+                    .iter()
+                    .map(|h| Cell::new(h).fg(Color::Green)),
+            );
+            // 新增和删除的行合并为一行显示非空的单元格
+            let summary = |row: &excel_cli::RowDiff, removed: bool| {
+                row.changes
+                    .iter()
+                    .map(|c| (&c.column, if removed { &c.before } else { &c.after }))
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(column, value)| format!("{}={}", column, value.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            for (kind, row) in diff.entries() {
+                let key = row.key.join(", ");
+                match kind {
+                    "removed" => {
+                        table.add_row(vec![
+                            Cell::new("删除").fg(Color::Red),
+                            Cell::new(&key),
+                            Cell::new(""),
+                            Cell::new(summary(row, true)).fg(Color::Red),
+                            Cell::new(""),
+                        ]);
+                    }
+                    "added" => {
+                        table.add_row(vec![
+                            Cell::new("新增").fg(Color::Green),
+                            Cell::new(&key),
+                            Cell::new(""),
+                            Cell::new(""),
+                            Cell::new(summary(row, false)).fg(Color::Green),
+                        ]);
+                    }
+                    _ => {
+                        for change in &row.changes {
+                            table.add_row(vec![
+                                Cell::new("修改").fg(Color::Yellow),
+                                Cell::new(&key),
+                                Cell::new(&change.column),
+                                Cell::new(change.before.to_string()).fg(Color::Red),
+                                Cell::new(change.after.to_string()).fg(Color::Green),
+                            ]);
+                        }
+                    }
+                }
+            }
+            table.to_string()
+        }
+        other => {
+            eprintln!("❌ 错误: 不支持的对比输出格式: {}", other);
+            eprintln!("支持的格式: table, json, csv, html, sql");
+            std::process::exit(1);
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &rendered)?;
+            println!("✅ 对比结果已生成! 输出文件: {}", path);
+        }
+        None => println!("\n{}", rendered),
+    }
+
+    Ok(())
+}
+
+/// 执行任务文件
+fn run_jobs(manifest: &str, threads: Option<usize>) -> Result<()> {
+    if !Path::new(manifest).exists() {
+        eprintln!("❌ 错误: 任务文件不存在: {}", manifest);
+        std::process::exit(1);
+    }
+
+    let file = JobFile::from_path(manifest)?;
+    let threads = threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    println!(
+        "🚀 正在执行 {} 个任务（并行 {}）: {}",
+        file.jobs.len(),
+        threads.min(file.jobs.len()),
+        manifest
+    );
+
+    let outcomes = file.run(threads);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(
+        ["任务", "状态", "工作表", "行数", "输出文件", "耗时"]
+            .iter()
+            .map(|h| Cell::new(h).fg(Color::Green)),
+    );
+    let mut failures = Vec::new();
+    for outcome in &outcomes {
+        let elapsed = format!("{:.2}s", outcome.elapsed.as_secs_f64());
+        match &outcome.result {
+            Ok(report) => {
+                table.add_row(vec![
+                    Cell::new(&outcome.name),
+                    Cell::new("✅ 成功").fg(Color::Green),
+                    Cell::new(report.sheets),
+                    Cell::new(report.rows),
+                    Cell::new(report.outputs.join("\n")),
+                    Cell::new(elapsed),
+                ]);
+            }
+            Err(e) => {
+                table.add_row(vec![
+                    Cell::new(&outcome.name),
+                    Cell::new("❌ 失败").fg(Color::Red),
+                    Cell::new(""),
+                    Cell::new(""),
+                    Cell::new(""),
+                    Cell::new(elapsed),
+                ]);
+                failures.push((&outcome.name, e));
+            }
+        }
+    }
+    println!("\n{}", table);
+
+    if !failures.is_empty() {
+        for (name, error) in &failures {
+            eprintln!("❌ 任务 '{}' 失败: {}", name, error);
+        }
+        eprintln!("{} / {} 个任务失败", failures.len(), outcomes.len());
+        std::process::exit(1);
+    }
+    println!("✅ 全部 {} 个任务完成!", outcomes.len());
+
+    Ok(())
+}
+
+/// 统计 Excel 数据的每一列
+fn profile_excel(
+    input: &str,
+    sheet_name: Option<&str>,
+    output: Option<&str>,
+    format: Option<&str>,
+    top: usize,
+) -> Result<()> {
+    // 检查输入文件是否存在
+    if !Path::new(input).exists() {
+        eprintln!("❌ 错误: 输入文件不存在: {}", input);
+        std::process::exit(1);
+    }
+
+    println!("📖 正在读取 Excel 文件: {}", input);
+    let data = ExcelReader::new(input).read_sheet(sheet_name)?;
+    println!(
+        "✅ 成功读取工作表 '{}': {} 行 × {} 列",
+        data.sheet_name,
+        data.row_count(),
+        data.column_count()
+    );
+
+    let profile = Profiler::new().with_top_n(top).profile(&data);
+
+    let format = report_format(format, output);
+    let rendered = match format.as_str() {
+        "json" => profile.to_json()?,
+        "html" | "htm" => profile.to_html()?,
+        "table" => {
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(
+                [
+                    "列",
+                    "类型",
+                    "空值",
+                    "不同值",
+                    "范围",
+                    "平均 / 中位数 / 标准差",
+                    "高频值",
+                ]
+                .iter()
+                .map(|h| Cell::new(h).fg(Color::Green)),
+            );
+            for column in &profile.columns {
+                let range = if let Some(dates) = &column.date_range {
+                    format!("{} ~ {}", dates.min, dates.max)
+                } else if let Some(text) = &column.text {
+                    format!("长度 {} ~ {}", text.min_length, text.max_length)
+                } else if let Some(stats) = &column.numeric {
+                    format!("{} ~ {}", round(stats.min), round(stats.max))
+                } else {
+                    String::new()
+                };
+                let stats = column
+                    .numeric
+                    .as_ref()
+                    .map(|s| {
+                        format!(
+                            "{} / {} / {}",
+                            round(s.mean),
+                            round(s.median),
+                            round(s.stddev)
+                        )
+                    })
+                    .unwrap_or_default();
+                let top_values = column
+                    .text
+                    .as_ref()
+                    .map(|t| {
+                        t.top_values
+                            .iter()
+                            .map(|v| format!("{} ({})", v.value, v.count))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    })
+                    .unwrap_or_default();
+                table.add_row(vec![
+                    Cell::new(&column.name),
+                    Cell::new(&column.inferred_type),
+                    Cell::new(format!(
+                        "{} ({:.1}%)",
+                        column.null_count,
+                        column.null_ratio * 100.0
+                    )),
+                    Cell::new(column.distinct_count),
+                    Cell::new(range),
+                    Cell::new(stats),
+                    Cell::new(top_values),
+                ]);
+            }
+            let mut out = table.to_string();
+            for column in profile.columns.iter() {
+                for warning in &column.warnings {
+                    out.push_str(&format!("\n⚠️  列 '{}' {}", column.name, warning));
+                }
+            }
+            out
+        }
+        other => {
+            eprintln!("❌ 错误: 不支持的统计输出格式: {}", other);
+            eprintln!("支持的格式: table, json, html");
+            std::process::exit(1);
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &rendered)?;
+            println!("✅ 统计结果已生成! 输出文件: {}", path);
+        }
+        None => println!("\n{}", rendered),
+    }
+
+    Ok(())
+}
+
+/// 成对的开关参数：都未指定时为 None，由配置文件决定
+fn switch(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// 报告的输出格式：未指定时按输出文件扩展名判断，没有输出文件时输出终端表格
+fn report_format(format: Option<&str>, output: Option<&str>) -> String {
+    match (format, output) {
+        (Some(format), _) => format.to_lowercase(),
+        (None, Some(path)) => Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default(),
+        (None, None) => "table".to_string(),
+    }
+}
+
+/// 保留 4 位小数并去掉多余的 0
+fn round(value: f64) -> f64 {
+    (value * 10000.0).round() / 10000.0
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use crate::error::{ExcelCliError, Result};
+use crate::models::{CellValue, ExcelData, ExcelRow};
+
+/// 来源文件列名
+pub const SOURCE_FILE_COLUMN: &str = "_source_file";
+/// 来源工作表列名
+pub const SOURCE_SHEET_COLUMN: &str = "_source_sheet";
+
+/// 合并多份数据时的列对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    /// 保留所有出现过的列，缺失的单元格为空
+    Union,
+    /// 只保留所有数据都包含的列
+    Intersection,
+}
+
+impl ColumnAlignment {
+    /// 从字符串解析列对齐方式
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "union" => Ok(ColumnAlignment::Union),
+            "intersection" => Ok(ColumnAlignment::Intersection),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的列对齐方式: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 数据合并器：按表头名称对齐多个文件或工作表的列，纵向拼接为一份数据
+pub struct DataMerger {
+    alignment: ColumnAlignment,
+    source_columns: bool,
+}
+
+impl Default for DataMerger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataMerger {
+    /// 创建新的合并器（默认取列的并集，不添加来源列）
+    pub fn new() -> Self {
+        Self {
+            alignment: ColumnAlignment::Union,
+            source_columns: false,
+        }
+    }
+
+    /// 设置列对齐方式
+    pub fn with_alignment(mut self, alignment: ColumnAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// 设置是否添加 `_source_file` 和 `_source_sheet` 列
+    pub fn with_source_columns(mut self, source_columns: bool) -> Self {
+        self.source_columns = source_columns;
+        self
+    }
+
+    /// 合并多份数据，`sources` 为 (来源文件, 数据)
+    ///
+    /// 表头去掉首尾空白后按名称对齐；同一列在合并后混有数值和文本时统一类型：
+    /// 文本都能解析为数字时转换为数值，否则全部转换为文本
+    pub fn merge(&self, sources: &[(String, ExcelData)]) -> Result<ExcelData> {
+        let Some((_, first)) = sources.first() else {
+            return Err(ExcelCliError::ExportError("没有要合并的数据".to_string()));
+        };
+
+        let mut headers: Vec<String> = Vec::new();
+        for (_, data) in sources {
+            for header in &data.headers {
+                let header = header.trim();
+                if !headers.iter().any(|h| h == header) {
+                    headers.push(header.to_string());
+                }
+            }
+        }
+        if self.alignment == ColumnAlignment::Intersection {
+            headers.retain(|header| {
+                sources
+                    .iter()
+                    .all(|(_, data)| data.headers.iter().any(|h| h.trim() == header))
+            });
+            if headers.is_empty() {
+                return Err(ExcelCliError::ExportError(
+                    "输入数据没有共同的列".to_string(),
+                ));
+            }
+        }
+
+        let mut merged = ExcelData::new(first.sheet_name.clone(), headers.clone());
+        if self.source_columns {
+            merged
+                .headers
+                .extend([SOURCE_FILE_COLUMN, SOURCE_SHEET_COLUMN].map(String::from));
+        }
+
+        for (file, data) in sources {
+            for row in &data.rows {
+                let values: HashMap<&str, &CellValue> = row
+                    .data
+                    .iter()
+                    .map(|(header, value)| (header.trim(), value))
+                    .collect();
+                let mut cells: HashMap<String, CellValue> = headers
+                    .iter()
+                    .map(|header| {
+                        let value = values.get(header.as_str()).copied().cloned();
+                        (header.clone(), value.unwrap_or(CellValue::Empty))
+                    })
+                    .collect();
+                if self.source_columns {
+                    cells.insert(
+                        SOURCE_FILE_COLUMN.to_string(),
+                        CellValue::String(file.clone()),
+                    );
+                    cells.insert(
+                        SOURCE_SHEET_COLUMN.to_string(),
+                        CellValue::String(data.sheet_name.clone()),
+                    );
+                }
+                merged.add_row(ExcelRow { data: cells });
+            }
+        }
+
+        for header in &headers {
+            reconcile_column(&mut merged.rows, header);
+        }
+        Ok(merged)
+    }
+}
+
+/// 统一一列的类型：混有数值和文本时，文本都是数字则转为数值，否则全部转为文本
+///
+/// 只有转换后原样写回的文本才算数字：`01234`、`1.50`、`1e3` 会丢失写法，`NaN`、`inf` 不是有限数值，
+/// 出现这些文本时整列转为文本
+fn reconcile_column(rows: &mut [ExcelRow], column: &str) {
+    let (mut numbers, mut booleans, mut texts, mut all_numeric) = (false, false, false, true);
+    for value in rows.iter().filter_map(|row| row.data.get(column)) {
+        match value {
+            CellValue::Number(_) => numbers = true,
+            CellValue::Boolean(_) => booleans = true,
+            CellValue::String(s) => {
+                texts = true;
+                all_numeric &= s
+                    .trim()
+                    .parse::<f64>()
+                    .is_ok_and(|n| n.is_finite() && n.to_string() == s.trim());
+            }
+            CellValue::Empty => {}
+        }
+    }
+    if !texts || !(numbers || booleans) {
+        return;
+    }
+
+    let to_number = all_numeric && !booleans;
+    for value in rows.iter_mut().filter_map(|row| row.data.get_mut(column)) {
+        *value = match value {
+            CellValue::String(s) if to_number => {
+                CellValue::Number(s.trim().parse().unwrap_or_default())
+            }
+            CellValue::Number(_) | CellValue::Boolean(_) if !to_number => {
+                CellValue::String(value.to_string())
+            }
+            _ => continue,
+        };
+    }
+}
+
+/// 展开输入路径中的通配符（如 `regions/*.xlsx`），结果按文件名排序
+///
+/// 不含通配符的路径原样保留；通配符没有匹配到文件时报错
+pub fn expand_inputs(patterns: &[String]) -> Result<Vec<String>> {
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            inputs.push(pattern.clone());
+            continue;
+        }
+        let paths = glob::glob(pattern).map_err(|e| {
+            ExcelCliError::ExportError(format!("无效的输入通配符 '{}': {}", pattern, e))
+        })?;
+        let mut matched: Vec<String> = paths
+            .filter_map(|path| path.ok())
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        if matched.is_empty() {
+            return Err(ExcelCliError::ExportError(format!(
+                "没有与 '{}' 匹配的输入文件",
+                pattern
+            )));
+        }
+        matched.sort();
+        inputs.extend(matched);
+    }
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_alignment_and_types() {
+        let north = ExcelData::from_rows(
+            "north",
+            &["Code", "Amount", "Region"],
+            &[&[
+                CellValue::text("A1"),
+                CellValue::Number(10.0),
+                CellValue::text("N"),
+            ]],
+        );
+        let south = ExcelData::from_rows(
+            "south",
+            &["Code ", "Amount", "Manager"],
+            &[&[
+                CellValue::text("B2"),
+                CellValue::text(" 7.5"),
+                CellValue::text("Li"),
+            ]],
+        );
+        let sources = vec![
+            ("north.xlsx".to_string(), north),
+            ("south.xlsx".to_string(), south),
+        ];
+
+        let merged = DataMerger::new()
+            .with_source_columns(true)
+            .merge(&sources)
+            .unwrap();
+        assert_eq!(
+            merged.headers,
+            vec![
+                "Code",
+                "Amount",
+                "Region",
+                "Manager",
+                "_source_file",
+                "_source_sheet"
+            ]
+        );
+        assert_eq!(merged.row_count(), 2);
+        let second = &merged.rows[1].data;
+        assert!(matches!(second["Amount"], CellValue::Number(n) if n == 7.5));
+        assert!(second["Region"].is_empty());
+        assert_eq!(second["_source_file"].to_string(), "south.xlsx");
+        assert_eq!(second["_source_sheet"].to_string(), "south");
+
+        let merged = DataMerger::new()
+            .with_alignment(ColumnAlignment::Intersection)
+            .merge(&sources)
+            .unwrap();
+        assert_eq!(merged.headers, vec!["Code", "Amount"]);
+    }
+
+    #[test]
+    fn test_reconcile_mixed_column_as_text() {
+        let a = ExcelData::from_rows("a", &["Zip"], &[&[CellValue::Number(100.0)]]);
+        let b = ExcelData::from_rows("b", &["Zip"], &[&[CellValue::text("01234-5")]]);
+        let merged = DataMerger::new()
+            .merge(&[("a".to_string(), a), ("b".to_string(), b)])
+            .unwrap();
+        assert!(matches!(&merged.rows[0].data["Zip"], CellValue::String(s) if s == "100"));
+        assert!(ColumnAlignment::from_str("outer").is_err());
+
+        // 转为数值会丢失前导零或不是有限数值的文本，整列保留为文本
+        for zip in ["01234", "1.50", "NaN", "inf"] {
+            let a = ExcelData::from_rows("a", &["Zip"], &[&[CellValue::Number(100.0)]]);
+            let b = ExcelData::from_rows("b", &["Zip"], &[&[CellValue::text(zip)]]);
+            let merged = DataMerger::new()
+                .merge(&[("a".to_string(), a), ("b".to_string(), b)])
+                .unwrap();
+            assert!(matches!(&merged.rows[1].data["Zip"], CellValue::String(s) if s == zip));
+            assert!(matches!(&merged.rows[0].data["Zip"], CellValue::String(s) if s == "100"));
+        }
+    }
+}
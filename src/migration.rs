@@ -0,0 +1,256 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+
+use crate::error::{ExcelCliError, Result};
+use crate::identifier::split_words;
+
+/// 迁移文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationFormat {
+    /// diesel：`{时间戳}_{名称}/up.sql` 和 `down.sql`
+    Diesel,
+    /// sqlx 可逆迁移：`{时间戳}_{名称}.up.sql` 和 `.down.sql`
+    Sqlx,
+    /// Flyway：`V{n}__{名称}.sql` 和撤销迁移 `U{n}__{名称}.sql`
+    Flyway,
+    /// Liquibase YAML changelog，回滚语句写在 changeset 的 rollback 中
+    Liquibase,
+}
+
+impl MigrationFormat {
+    /// 从字符串解析迁移格式
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "diesel" => Ok(MigrationFormat::Diesel),
+            "sqlx" => Ok(MigrationFormat::Sqlx),
+            "flyway" => Ok(MigrationFormat::Flyway),
+            "liquibase" => Ok(MigrationFormat::Liquibase),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的迁移格式: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// 迁移文件生成器：把 up/down 语句按迁移框架的目录和命名约定写入文件
+pub struct MigrationWriter {
+    format: MigrationFormat,
+    directory: PathBuf,
+    version: Option<u64>,
+    timestamp: NaiveDateTime,
+}
+
+impl MigrationWriter {
+    /// 创建新的迁移文件生成器，时间戳默认为当前 UTC 时间
+    pub fn new<P: AsRef<Path>>(format: MigrationFormat, directory: P) -> Self {
+        Self {
+            format,
+            directory: directory.as_ref().to_path_buf(),
+            version: None,
+            timestamp: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    /// 设置 Flyway 版本号（默认为目录中已有的最大版本号加 1）
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// 设置迁移时间戳
+    pub fn with_timestamp(mut self, timestamp: NaiveDateTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// 生成迁移文件的路径和内容，`name` 会转换为 snake_case
+    pub fn files(&self, name: &str, up: &str, down: &str) -> Result<Vec<(PathBuf, String)>> {
+        let mut name = split_words(&deunicode::deunicode(name), true)
+            .join("_")
+            .to_lowercase();
+        if name.is_empty() {
+            name = "migration".to_string();
+        }
+        let up = format!("{}\n", up.trim_end());
+        let down = format!("{}\n", down.trim_end());
+        let dir = &self.directory;
+
+        let files = match self.format {
+            MigrationFormat::Diesel => {
+                let folder = dir.join(format!(
+                    "{}_{}",
+                    self.timestamp.format("%Y-%m-%d-%H%M%S"),
+                    name
+                ));
+                vec![(folder.join("up.sql"), up), (folder.join("down.sql"), down)]
+            }
+            MigrationFormat::Sqlx => {
+                let prefix = format!("{}_{}", self.timestamp.format("%Y%m%d%H%M%S"), name);
+                vec![
+                    (dir.join(format!("{}.up.sql", prefix)), up),
+                    (dir.join(format!("{}.down.sql", prefix)), down),
+                ]
+            }
+            MigrationFormat::Flyway => {
+                let version = match self.version {
+                    Some(version) => version,
+                    None => self.next_flyway_version()?,
+                };
+                vec![
+                    (dir.join(format!("V{}__{}.sql", version, name)), up),
+                    (dir.join(format!("U{}__{}.sql", version, name)), down),
+                ]
+            }
+            MigrationFormat::Liquibase => {
+                let id = format!("{}-{}", self.timestamp.format("%Y%m%d%H%M%S"), name);
+                let changelog = format!(
+                    "databaseChangeLog:\n  - changeSet:\n      id: {}\n      author: excel-cli\n      changes:\n        - sql:\n            sql: |\n{}      rollback:\n        - sql:\n            sql: |\n{}",
+                    yaml_scalar(&id),
+                    indent(&up, 14),
+                    indent(&down, 14)
+                );
+                vec![(
+                    dir.join(format!(
+                        "{}_{}.yaml",
+                        self.timestamp.format("%Y%m%d%H%M%S"),
+                        name
+                    )),
+                    changelog,
+                )]
+            }
+        };
+        Ok(files)
+    }
+
+    /// 写入迁移文件，已存在同名文件时报错而不覆盖
+    pub fn write(&self, name: &str, up: &str, down: &str) -> Result<Vec<PathBuf>> {
+        let files = self.files(name, up, down)?;
+        if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+            return Err(ExcelCliError::ExportError(format!(
+                "迁移文件已存在: {}",
+                path.display()
+            )));
+        }
+        for (path, content) in &files {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        Ok(files.into_iter().map(|(path, _)| path).collect())
+    }
+
+    /// 目录中已有的 `V{n}__*.sql` 的最大版本号加 1
+    fn next_flyway_version(&self) -> Result<u64> {
+        if !self.directory.is_dir() {
+            return Ok(1);
+        }
+        let mut max = 0;
+        for entry in std::fs::read_dir(&self.directory)? {
+            let file_name = entry?.file_name().to_string_lossy().to_string();
+            let version = file_name
+                .strip_prefix('V')
+                .and_then(|rest| rest.split_once("__"))
+                .and_then(|(version, _)| version.parse::<u64>().ok());
+            if let Some(version) = version {
+                max = max.max(version);
+            }
+        }
+        Ok(max + 1)
+    }
+}
+
+/// 缩进多行文本，用作 YAML 块标量的内容
+fn indent(text: &str, spaces: usize) -> String {
+    let padding = " ".repeat(spaces);
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", padding, line)
+            }
+        })
+        .collect()
+}
+
+/// YAML 单行标量，包含特殊字符时加双引号
+fn yaml_scalar(s: &str) -> String {
+    if s.chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        s.to_string()
+    } else {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_files() {
+        let timestamp =
+            NaiveDateTime::parse_from_str("2026-10-18 09:30:05", "%Y-%m-%d %H:%M:%S").unwrap();
+        let up = "CREATE TABLE \"users\" (\n    \"id\" INTEGER PRIMARY KEY\n);";
+        let down = "DROP TABLE IF EXISTS \"users\";";
+
+        let files = MigrationWriter::new(MigrationFormat::Diesel, "migrations")
+            .with_timestamp(timestamp)
+            .files("create Users", up, down)
+            .unwrap();
+        assert_eq!(
+            files[0].0,
+            PathBuf::from("migrations/2026-10-18-093005_create_users/up.sql")
+        );
+        assert_eq!(files[1].1, "DROP TABLE IF EXISTS \"users\";\n");
+
+        let files = MigrationWriter::new(MigrationFormat::Sqlx, "migrations")
+            .with_timestamp(timestamp)
+            .files("create_users", up, down)
+            .unwrap();
+        assert_eq!(
+            files[1].0,
+            PathBuf::from("migrations/20261018093005_create_users.down.sql")
+        );
+
+        let files = MigrationWriter::new(MigrationFormat::Flyway, "sql")
+            .with_version(7)
+            .files("create_users", up, down)
+            .unwrap();
+        assert_eq!(files[0].0, PathBuf::from("sql/V7__create_users.sql"));
+        assert_eq!(files[1].0, PathBuf::from("sql/U7__create_users.sql"));
+
+        let files = MigrationWriter::new(MigrationFormat::Liquibase, "changelog")
+            .with_timestamp(timestamp)
+            .files("create_users", up, down)
+            .unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].1,
+            "databaseChangeLog:\n  - changeSet:\n      id: 20261018093005-create_users\n      author: excel-cli\n      changes:\n        - sql:\n            sql: |\n              CREATE TABLE \"users\" (\n                  \"id\" INTEGER PRIMARY KEY\n              );\n      rollback:\n        - sql:\n            sql: |\n              DROP TABLE IF EXISTS \"users\";\n"
+        );
+    }
+
+    #[test]
+    fn test_next_flyway_version() {
+        let dir = std::env::temp_dir().join(format!("excel_cli_flyway_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("V3__create_orders.sql"), "").unwrap();
+        std::fs::write(dir.join("V12__add_index.sql"), "").unwrap();
+        std::fs::write(dir.join("R__views.sql"), "").unwrap();
+
+        let writer = MigrationWriter::new(MigrationFormat::Flyway, &dir);
+        let paths = writer
+            .write("create_users", "SELECT 1;", "SELECT 2;")
+            .unwrap();
+        assert!(paths[0].ends_with("V13__create_users.sql"));
+        let existing = MigrationWriter::new(MigrationFormat::Flyway, &dir).with_version(13);
+        assert!(existing.write("create_users", "SELECT 1;", "").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
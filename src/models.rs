@@ -23,7 +23,14 @@ pub enum CellValue {
 }
 
 impl CellValue {
+    /// 创建文本值（用于测试）
+    #[cfg(test)]
+    pub fn text(s: &str) -> Self {
+        CellValue::String(s.to_string())
+    }
+
     /// 转换为字符串
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         match self {
             CellValue::String(s) => s.clone(),
@@ -74,6 +81,25 @@ impl ExcelData {
     pub fn column_count(&self) -> usize {
         self.headers.len()
     }
+
+    /// 从表头和按表头顺序排列的行创建数据（用于测试）
+    #[cfg(test)]
+    pub fn from_rows(sheet_name: &str, headers: &[&str], rows: &[&[CellValue]]) -> Self {
+        let mut data = Self::new(
+            sheet_name.to_string(),
+            headers.iter().map(|h| h.to_string()).collect(),
+        );
+        for values in rows {
+            data.add_row(ExcelRow {
+                data: headers
+                    .iter()
+                    .map(|h| h.to_string())
+                    .zip(values.iter().cloned())
+                    .collect(),
+            });
+        }
+        data
+    }
 }
 
 impl ExcelRow {
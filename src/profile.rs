@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tera::Context;
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::template::render_html_report;
+use crate::models::{CellValue, ExcelData};
+use crate::schema::{parse_bool, parse_date, parse_datetime, SqlType, TypeInference};
+
+/// 数值列的统计量
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumericStats {
+    /// 最小值
+    pub min: f64,
+    /// 最大值
+    pub max: f64,
+    /// 平均值
+    pub mean: f64,
+    /// 中位数
+    pub median: f64,
+    /// 样本标准差
+    pub stddev: f64,
+}
+
+/// 值及其出现次数
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueCount {
+    /// 值
+    pub value: String,
+    /// 出现次数
+    pub count: usize,
+}
+
+/// 字符串列的统计量
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TextStats {
+    /// 最短长度（字符数）
+    pub min_length: usize,
+    /// 最长长度（字符数）
+    pub max_length: usize,
+    /// 出现次数最多的值，按次数降序
+    pub top_values: Vec<ValueCount>,
+}
+
+/// 日期列的范围
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DateRange {
+    /// 最早日期
+    pub min: String,
+    /// 最晚日期
+    pub max: String,
+}
+
+/// 单列的统计信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnProfile {
+    /// 列名
+    pub name: String,
+    /// 推断的类型
+    pub inferred_type: String,
+    /// 非空值个数
+    pub count: usize,
+    /// 空值个数
+    pub null_count: usize,
+    /// 空值比例（0~1）
+    pub null_ratio: f64,
+    /// 不同值个数（不含空值）
+    pub distinct_count: usize,
+    /// 数值统计（列中有数值时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<NumericStats>,
+    /// 字符串统计（推断为字符串时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<TextStats>,
+    /// 日期范围（推断为日期或日期时间时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<DateRange>,
+    /// 警告，如混合类型
+    pub warnings: Vec<String>,
+}
+
+/// 工作表的统计信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DataProfile {
+    /// 工作表名称
+    pub sheet_name: String,
+    /// 数据行数
+    pub row_count: usize,
+    /// 列数
+    pub column_count: usize,
+    /// 各列统计
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl DataProfile {
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 渲染为 HTML 报告
+    pub fn to_html(&self) -> Result<String> {
+        let context = Context::from_serialize(self)
+            .map_err(|e| ExcelCliError::ExportError(format!("模板渲染失败: {}", e)))?;
+        render_html_report(
+            "profile",
+            include_str!("../templates/profile.tera"),
+            &context,
+        )
+    }
+}
+
+/// 数据统计分析器
+pub struct Profiler {
+    top_n: usize,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    /// 创建新的分析器（默认列出前 5 个高频值）
+    pub fn new() -> Self {
+        Self { top_n: 5 }
+    }
+
+    /// 设置列出的高频值个数
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// 统计每一列
+    pub fn profile(&self, data: &ExcelData) -> DataProfile {
+        DataProfile {
+            sheet_name: data.sheet_name.clone(),
+            row_count: data.row_count(),
+            column_count: data.column_count(),
+            columns: data
+                .headers
+                .iter()
+                .map(|header| self.profile_column(data, header))
+                .collect(),
+        }
+    }
+
+    fn profile_column(&self, data: &ExcelData, column: &str) -> ColumnProfile {
+        let values: Vec<&CellValue> = data
+            .rows
+            .iter()
+            .filter_map(|row| row.data.get(column))
+            .filter(|v| !v.is_empty())
+            .collect();
+        let null_count = data.row_count() - values.len();
+        let sql_type = TypeInference::infer_column_type(data, column);
+
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        for value in &values {
+            *frequencies.entry(value.to_string()).or_default() += 1;
+        }
+
+        let numbers: Vec<f64> = values
+            .iter()
+            .filter_map(|v| match v {
+                CellValue::Number(n) if n.is_finite() => Some(*n),
+                _ => None,
+            })
+            .collect();
+
+        let text = matches!(sql_type, SqlType::Varchar(_) | SqlType::Text).then(|| {
+            let lengths: Vec<usize> = values
+                .iter()
+                .map(|v| v.to_string().chars().count())
+                .collect();
+            let mut top_values: Vec<ValueCount> = frequencies
+                .iter()
+                .map(|(value, count)| ValueCount {
+                    value: value.clone(),
+                    count: *count,
+                })
+                .collect();
+            // 次数相同时按值排序，保证输出稳定
+            top_values.sort_by(|a, b| b.count.cmp(&a.count).then(a.value.cmp(&b.value)));
+            top_values.truncate(self.top_n);
+            TextStats {
+                min_length: lengths.iter().copied().min().unwrap_or(0),
+                max_length: lengths.iter().copied().max().unwrap_or(0),
+                top_values,
+            }
+        });
+
+        let date_range = matches!(sql_type, SqlType::Date | SqlType::DateTime)
+            .then(|| {
+                let dates: Vec<_> = values
+                    .iter()
+                    .filter_map(|v| match v {
+                        CellValue::String(s) => parse_datetime(s),
+                        _ => None,
+                    })
+                    .collect();
+                let format = if sql_type == SqlType::Date {
+                    "%Y-%m-%d"
+                } else {
+                    "%Y-%m-%d %H:%M:%S"
+                };
+                Some(DateRange {
+                    min: dates.iter().min()?.format(format).to_string(),
+                    max: dates.iter().max()?.format(format).to_string(),
+                })
+            })
+            .flatten();
+
+        ColumnProfile {
+            name: column.to_string(),
+            inferred_type: type_label(&sql_type),
+            count: values.len(),
+            null_count,
+            null_ratio: if data.row_count() == 0 {
+                0.0
+            } else {
+                null_count as f64 / data.row_count() as f64
+            },
+            distinct_count: frequencies.len(),
+            numeric: numeric_stats(&numbers),
+            text,
+            date_range,
+            warnings: mixed_type_warning(&values).into_iter().collect(),
+        }
+    }
+}
+
+/// 与方言无关的类型名
+fn type_label(sql_type: &SqlType) -> String {
+    match sql_type {
+        SqlType::Integer => "INTEGER".to_string(),
+        SqlType::BigInt => "BIGINT".to_string(),
+        SqlType::Float => "FLOAT".to_string(),
+        SqlType::Double => "DOUBLE".to_string(),
+        SqlType::Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
+        SqlType::Boolean => "BOOLEAN".to_string(),
+        SqlType::Varchar(len) => format!("VARCHAR({})", len),
+        SqlType::Text => "TEXT".to_string(),
+        SqlType::DateTime => "DATETIME".to_string(),
+        SqlType::Date => "DATE".to_string(),
+    }
+}
+
+fn numeric_stats(numbers: &[f64]) -> Option<NumericStats> {
+    if numbers.is_empty() {
+        return None;
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len();
+    let mean = sorted.iter().sum::<f64>() / n as f64;
+    let median = if n.is_multiple_of(2) {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    } else {
+        sorted[n / 2]
+    };
+    let stddev = if n > 1 {
+        (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    Some(NumericStats {
+        min: sorted[0],
+        max: sorted[n - 1],
+        mean,
+        median,
+        stddev,
+    })
+}
+
+/// 单元格值的种类
+fn value_kind(value: &CellValue) -> &'static str {
+    match value {
+        CellValue::Number(_) => "数值",
+        CellValue::Boolean(_) => "布尔",
+        CellValue::String(s) if parse_bool(s).is_some() => "布尔",
+        CellValue::String(s) if parse_date(s).is_some() || parse_datetime(s).is_some() => "日期",
+        CellValue::String(_) => "文本",
+        CellValue::Empty => "空",
+    }
+}
+
+/// 列中同时出现多种值时的警告，列出各种类的个数和少数种类的示例
+fn mixed_type_warning(values: &[&CellValue]) -> Option<String> {
+    let mut kinds: Vec<(&str, usize)> = Vec::new();
+    for value in values {
+        let kind = value_kind(value);
+        match kinds.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, count)) => *count += 1,
+            None => kinds.push((kind, 1)),
+        }
+    }
+    if kinds.len() < 2 {
+        return None;
+    }
+    kinds.sort_by_key(|kind| std::cmp::Reverse(kind.1));
+    let majority = kinds[0].0;
+    let samples: Vec<String> = values
+        .iter()
+        .filter(|v| value_kind(v) != majority)
+        .take(3)
+        .map(|v| format!("'{}'", v.to_string()))
+        .collect();
+    let counts: Vec<String> = kinds
+        .iter()
+        .map(|(kind, count)| format!("{} {}", kind, count))
+        .collect();
+    Some(format!(
+        "混合类型: {}（如 {}）",
+        counts.join("，"),
+        samples.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ExcelRow;
+
+    #[test]
+    fn test_profile() {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["amount".to_string(), "city".to_string(), "day".to_string()],
+        );
+        let rows = [
+            (CellValue::Number(10.0), "北京", "2024-03-01"),
+            (CellValue::Number(20.0), "上海", "2024-01-15"),
+            (CellValue::Number(40.0), "北京", ""),
+            (CellValue::String("N/A".to_string()), "北京", "2024-02-10"),
+        ];
+        for (amount, city, day) in rows {
+            let mut row = HashMap::new();
+            row.insert("amount".to_string(), amount);
+            row.insert("city".to_string(), CellValue::String(city.to_string()));
+            let day = if day.is_empty() {
+                CellValue::Empty
+            } else {
+                CellValue::String(day.to_string())
+            };
+            row.insert("day".to_string(), day);
+            data.add_row(ExcelRow { data: row });
+        }
+
+        let profile = Profiler::new().with_top_n(1).profile(&data);
+        let amount = &profile.columns[0];
+        let stats = amount.numeric.as_ref().unwrap();
+        assert_eq!((stats.min, stats.max, stats.median), (10.0, 40.0, 20.0));
+        assert!((stats.mean - 70.0 / 3.0).abs() < 1e-9);
+        assert!((stats.stddev - 15.275252316519467).abs() < 1e-9);
+        assert_eq!(
+            amount.warnings,
+            vec!["混合类型: 数值 3，文本 1（如 'N/A'）"]
+        );
+
+        let city = &profile.columns[1];
+        assert_eq!(city.distinct_count, 2);
+        let text = city.text.as_ref().unwrap();
+        assert_eq!((text.min_length, text.max_length), (2, 2));
+        assert_eq!(
+            text.top_values,
+            vec![ValueCount {
+                value: "北京".to_string(),
+                count: 3
+            }]
+        );
+
+        let day = &profile.columns[2];
+        assert_eq!(day.inferred_type, "DATE");
+        assert_eq!((day.null_count, day.null_ratio), (1, 0.25));
+        assert_eq!(
+            day.date_range,
+            Some(DateRange {
+                min: "2024-01-15".to_string(),
+                max: "2024-03-01".to_string()
+            })
+        );
+
+        let html = profile.to_html().unwrap();
+        assert!(html.contains("<td>amount</td>"));
+        assert!(html.contains("&#x27;N&#x2F;A&#x27;"));
+    }
+}
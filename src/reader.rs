@@ -1,4 +1,4 @@
-use calamine::{open_workbook, Data, Reader, Xlsx};
+use calamine::{open_workbook, Data, ExcelDateTime, Reader, Xlsx};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -91,7 +91,7 @@ impl ExcelReader {
             Data::Int(i) => i.to_string(),
             Data::Bool(b) => b.to_string(),
             Data::Error(e) => format!("Error: {:?}", e),
-            Data::DateTime(dt) => format!("{:?}", dt),
+            Data::DateTime(dt) => format_datetime(dt),
             Data::DateTimeIso(dt) => dt.clone(),
             Data::DurationIso(d) => d.clone(),
         }
@@ -106,13 +106,36 @@ impl ExcelReader {
             Data::Int(i) => CellValue::Number(*i as f64),
             Data::Bool(b) => CellValue::Boolean(*b),
             Data::Error(e) => CellValue::String(format!("Error: {:?}", e)),
-            Data::DateTime(dt) => CellValue::String(format!("{:?}", dt)),
+            Data::DateTime(dt) => CellValue::String(format_datetime(dt)),
             Data::DateTimeIso(dt) => CellValue::String(dt.clone()),
             Data::DurationIso(d) => CellValue::String(d.clone()),
         }
     }
 }
 
+/// 将日期单元格转换为 ISO 8601 文本
+///
+/// 没有时间部分时只输出日期，只有时间（序列号小于 1）时只输出时间，时长格式输出为 `时:分:秒`
+fn format_datetime(dt: &ExcelDateTime) -> String {
+    if dt.is_duration() {
+        let seconds = dt.as_duration().map_or(0, |d| d.num_seconds());
+        return format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        );
+    }
+    match dt.as_datetime() {
+        Some(datetime) if dt.as_f64() < 1.0 => datetime.format("%H:%M:%S").to_string(),
+        Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => {
+            datetime.format("%Y-%m-%d").to_string()
+        }
+        Some(datetime) => datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => dt.as_f64().to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +159,24 @@ mod tests {
             CellValue::Number(_)
         ));
     }
+
+    #[test]
+    fn test_date_cells_as_iso_text() {
+        use calamine::ExcelDateTimeType::{DateTime, TimeDelta};
+
+        let reader = ExcelReader::new("test.xlsx");
+        let cases = [
+            (45352.0, DateTime, "2024-03-01"),
+            (45352.5, DateTime, "2024-03-01 12:00:00"),
+            (0.75, DateTime, "18:00:00"),
+            (1.5, TimeDelta, "36:00:00"),
+        ];
+        for (serial, kind, expected) in cases {
+            let cell = Data::DateTime(ExcelDateTime::new(serial, kind, false));
+            match reader.cell_to_value(&cell) {
+                CellValue::String(s) => assert_eq!(s, expected),
+                other => panic!("日期单元格应为文本: {:?}", other),
+            }
+        }
+    }
 }
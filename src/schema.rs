@@ -1,12 +1,19 @@
-use crate::error::Result;
-use crate::exporter::sql::SqlDialect;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::sql::{check_identifier, SqlDialect};
+use crate::identifier::IdentifierNormalizer;
 use crate::models::{CellValue, ExcelData};
 
 /// 数据类型推断器
 pub struct TypeInference;
 
 /// SQL 数据类型
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SqlType {
     /// 整数
     Integer,
@@ -16,6 +23,8 @@ pub enum SqlType {
     Float,
     /// 双精度浮点
     Double,
+    /// 定点小数（精度，小数位数）
+    Decimal(usize, usize),
     /// 布尔
     Boolean,
     /// 可变字符串
@@ -37,127 +46,427 @@ impl SqlType {
             (SqlType::Integer, SqlDialect::SQLite) => "INTEGER".to_string(),
             (SqlType::Integer, SqlDialect::SqlServer) => "INT".to_string(),
             (SqlType::Integer, SqlDialect::Oracle) => "NUMBER(10)".to_string(),
+            (SqlType::Integer, SqlDialect::ClickHouse) => "Int32".to_string(),
+            (SqlType::Integer, SqlDialect::DuckDB) => "INTEGER".to_string(),
+            (SqlType::Integer, SqlDialect::Snowflake) => "INTEGER".to_string(),
+            (SqlType::Integer, SqlDialect::BigQuery) => "INT64".to_string(),
 
             (SqlType::BigInt, SqlDialect::MySQL) => "BIGINT".to_string(),
             (SqlType::BigInt, SqlDialect::PostgreSQL) => "BIGINT".to_string(),
             (SqlType::BigInt, SqlDialect::SQLite) => "INTEGER".to_string(),
             (SqlType::BigInt, SqlDialect::SqlServer) => "BIGINT".to_string(),
             (SqlType::BigInt, SqlDialect::Oracle) => "NUMBER(19)".to_string(),
+            (SqlType::BigInt, SqlDialect::ClickHouse) => "Int64".to_string(),
+            (SqlType::BigInt, SqlDialect::DuckDB) => "BIGINT".to_string(),
+            (SqlType::BigInt, SqlDialect::Snowflake) => "BIGINT".to_string(),
+            (SqlType::BigInt, SqlDialect::BigQuery) => "INT64".to_string(),
 
             (SqlType::Float, SqlDialect::MySQL) => "FLOAT".to_string(),
             (SqlType::Float, SqlDialect::PostgreSQL) => "REAL".to_string(),
             (SqlType::Float, SqlDialect::SQLite) => "REAL".to_string(),
             (SqlType::Float, SqlDialect::SqlServer) => "FLOAT".to_string(),
             (SqlType::Float, SqlDialect::Oracle) => "BINARY_FLOAT".to_string(),
+            (SqlType::Float, SqlDialect::ClickHouse) => "Float32".to_string(),
+            (SqlType::Float, SqlDialect::DuckDB) => "REAL".to_string(),
+            (SqlType::Float, SqlDialect::Snowflake) => "FLOAT".to_string(),
+            (SqlType::Float, SqlDialect::BigQuery) => "FLOAT64".to_string(),
 
             (SqlType::Double, SqlDialect::MySQL) => "DOUBLE".to_string(),
             (SqlType::Double, SqlDialect::PostgreSQL) => "DOUBLE PRECISION".to_string(),
             (SqlType::Double, SqlDialect::SQLite) => "REAL".to_string(),
             (SqlType::Double, SqlDialect::SqlServer) => "FLOAT".to_string(),
             (SqlType::Double, SqlDialect::Oracle) => "BINARY_DOUBLE".to_string(),
+            (SqlType::Double, SqlDialect::ClickHouse) => "Float64".to_string(),
+            (SqlType::Double, SqlDialect::DuckDB) => "DOUBLE".to_string(),
+            (SqlType::Double, SqlDialect::Snowflake) => "DOUBLE".to_string(),
+            (SqlType::Double, SqlDialect::BigQuery) => "FLOAT64".to_string(),
+
+            (SqlType::Decimal(p, s), SqlDialect::MySQL) => format!("DECIMAL({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::PostgreSQL) => format!("NUMERIC({}, {})", p, s),
+            (SqlType::Decimal(_, _), SqlDialect::SQLite) => "NUMERIC".to_string(),
+            (SqlType::Decimal(p, s), SqlDialect::SqlServer) => format!("DECIMAL({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::Oracle) => format!("NUMBER({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::ClickHouse) => format!("Decimal({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::DuckDB) => format!("DECIMAL({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::Snowflake) => format!("NUMBER({}, {})", p, s),
+            (SqlType::Decimal(p, s), SqlDialect::BigQuery) => format!("NUMERIC({}, {})", p, s),
 
             (SqlType::Boolean, SqlDialect::MySQL) => "BOOLEAN".to_string(),
             (SqlType::Boolean, SqlDialect::PostgreSQL) => "BOOLEAN".to_string(),
             (SqlType::Boolean, SqlDialect::SQLite) => "INTEGER".to_string(),
             (SqlType::Boolean, SqlDialect::SqlServer) => "BIT".to_string(),
             (SqlType::Boolean, SqlDialect::Oracle) => "NUMBER(1)".to_string(),
+            (SqlType::Boolean, SqlDialect::ClickHouse) => "Bool".to_string(),
+            (SqlType::Boolean, SqlDialect::DuckDB) => "BOOLEAN".to_string(),
+            (SqlType::Boolean, SqlDialect::Snowflake) => "BOOLEAN".to_string(),
+            (SqlType::Boolean, SqlDialect::BigQuery) => "BOOL".to_string(),
 
             (SqlType::Varchar(len), SqlDialect::MySQL) => format!("VARCHAR({})", len),
             (SqlType::Varchar(len), SqlDialect::PostgreSQL) => format!("VARCHAR({})", len),
             (SqlType::Varchar(_len), SqlDialect::SQLite) => "TEXT".to_string(),
             (SqlType::Varchar(len), SqlDialect::SqlServer) => format!("VARCHAR({})", len),
             (SqlType::Varchar(len), SqlDialect::Oracle) => format!("VARCHAR2({})", len),
+            (SqlType::Varchar(_len), SqlDialect::ClickHouse) => "String".to_string(),
+            (SqlType::Varchar(_len), SqlDialect::DuckDB) => "VARCHAR".to_string(),
+            (SqlType::Varchar(len), SqlDialect::Snowflake) => format!("VARCHAR({})", len),
+            (SqlType::Varchar(_len), SqlDialect::BigQuery) => "STRING".to_string(),
 
             (SqlType::Text, SqlDialect::MySQL) => "TEXT".to_string(),
             (SqlType::Text, SqlDialect::PostgreSQL) => "TEXT".to_string(),
             (SqlType::Text, SqlDialect::SQLite) => "TEXT".to_string(),
             (SqlType::Text, SqlDialect::SqlServer) => "NVARCHAR(MAX)".to_string(),
             (SqlType::Text, SqlDialect::Oracle) => "CLOB".to_string(),
+            (SqlType::Text, SqlDialect::ClickHouse) => "String".to_string(),
+            (SqlType::Text, SqlDialect::DuckDB) => "VARCHAR".to_string(),
+            (SqlType::Text, SqlDialect::Snowflake) => "VARCHAR".to_string(),
+            (SqlType::Text, SqlDialect::BigQuery) => "STRING".to_string(),
 
             (SqlType::DateTime, SqlDialect::MySQL) => "DATETIME".to_string(),
             (SqlType::DateTime, SqlDialect::PostgreSQL) => "TIMESTAMP".to_string(),
             (SqlType::DateTime, SqlDialect::SQLite) => "TEXT".to_string(),
             (SqlType::DateTime, SqlDialect::SqlServer) => "DATETIME2".to_string(),
             (SqlType::DateTime, SqlDialect::Oracle) => "TIMESTAMP".to_string(),
+            (SqlType::DateTime, SqlDialect::ClickHouse) => "DateTime".to_string(),
+            (SqlType::DateTime, SqlDialect::DuckDB) => "TIMESTAMP".to_string(),
+            (SqlType::DateTime, SqlDialect::Snowflake) => "TIMESTAMP_NTZ".to_string(),
+            (SqlType::DateTime, SqlDialect::BigQuery) => "DATETIME".to_string(),
 
             (SqlType::Date, SqlDialect::MySQL) => "DATE".to_string(),
             (SqlType::Date, SqlDialect::PostgreSQL) => "DATE".to_string(),
             (SqlType::Date, SqlDialect::SQLite) => "TEXT".to_string(),
             (SqlType::Date, SqlDialect::SqlServer) => "DATE".to_string(),
             (SqlType::Date, SqlDialect::Oracle) => "DATE".to_string(),
+            (SqlType::Date, SqlDialect::ClickHouse) => "Date".to_string(),
+            (SqlType::Date, SqlDialect::DuckDB) => "DATE".to_string(),
+            (SqlType::Date, SqlDialect::Snowflake) => "DATE".to_string(),
+            (SqlType::Date, SqlDialect::BigQuery) => "DATE".to_string(),
         }
     }
 }
 
+/// 单列的类型推断结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInference {
+    /// 推断的 SQL 类型
+    pub sql_type: SqlType,
+    /// 不符合推断类型的值：(数据行号，从 1 开始；原始值)
+    pub outliers: Vec<(usize, String)>,
+}
+
+/// 小数位数不超过此值时推断为 DECIMAL，否则为 DOUBLE
+const MAX_DECIMAL_SCALE: usize = 6;
+
+/// DECIMAL 的最大精度（各方言中最小的上限）
+const MAX_DECIMAL_PRECISION: usize = 38;
+
 impl TypeInference {
-    /// 推断列的 SQL 类型
+    /// 推断列的 SQL 类型（所有非空值都必须符合推断的类型）
     pub fn infer_column_type(data: &ExcelData, column: &str) -> SqlType {
-        let mut has_number = false;
-        let mut has_float = false;
-        let mut has_boolean = false;
-        let mut has_string = false;
-        let mut max_string_len = 0;
-        let mut all_empty = true;
-
-        for row in &data.rows {
-            if let Some(value) = row.data.get(column) {
-                match value {
-                    CellValue::Number(n) => {
-                        all_empty = false;
-                        has_number = true;
-                        if n.fract() != 0.0 {
-                            has_float = true;
-                        }
-                    }
-                    CellValue::Boolean(_) => {
-                        all_empty = false;
-                        has_boolean = true;
-                    }
-                    CellValue::String(s) => {
-                        all_empty = false;
-                        has_string = true;
-                        max_string_len = max_string_len.max(s.len());
+        Self::infer_column(data, column, 1.0).sql_type
+    }
+
+    /// 推断列的 SQL 类型，允许一定比例的值不符合
+    ///
+    /// `tolerance` 为非空值中必须符合推断类型的最小比例，例如 0.99 表示 99% 的值为数值时
+    /// 仍推断为数值类型，其余值作为异常值返回。依次尝试布尔、数值、日期，都不满足时为字符串
+    pub fn infer_column(data: &ExcelData, column: &str, tolerance: f64) -> ColumnInference {
+        let cells: Vec<(usize, &CellValue)> = data
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| row.data.get(column).map(|v| (i + 1, v)))
+            .filter(|(_, v)| !v.is_empty())
+            .collect();
+
+        // 如果全是空值，默认为 VARCHAR
+        if cells.is_empty() {
+            return ColumnInference {
+                sql_type: SqlType::Varchar(255),
+                outliers: Vec::new(),
+            };
+        }
+
+        let required = ((cells.len() as f64) * tolerance.clamp(0.0, 1.0)).ceil() as usize;
+        let required = required.max(1);
+        let matching = |accept: &dyn Fn(&CellValue) -> bool| -> Option<Vec<(usize, String)>> {
+            let outliers: Vec<(usize, String)> = cells
+                .iter()
+                .filter(|(_, v)| !accept(v))
+                .map(|(row, v)| (*row, v.to_string()))
+                .collect();
+            (cells.len() - outliers.len() >= required).then_some(outliers)
+        };
+
+        // 布尔单元格，或 true/false/yes/no/y/n 文本
+        let is_bool = |v: &CellValue| match v {
+            CellValue::Boolean(_) => true,
+            CellValue::String(s) => parse_bool(s).is_some(),
+            _ => false,
+        };
+        if let Some(outliers) = matching(&is_bool) {
+            return ColumnInference {
+                sql_type: SqlType::Boolean,
+                outliers,
+            };
+        }
+
+        // 数值（布尔值按 0/1 处理）
+        let is_number = |v: &CellValue| {
+            matches!(v, CellValue::Number(n) if n.is_finite()) || matches!(v, CellValue::Boolean(_))
+        };
+        if let Some(outliers) = matching(&is_number) {
+            let numbers: Vec<f64> = cells
+                .iter()
+                .filter_map(|(_, v)| match v {
+                    CellValue::Number(n) if n.is_finite() => Some(*n),
+                    _ => None,
+                })
+                .collect();
+            return ColumnInference {
+                sql_type: Self::numeric_type(&numbers),
+                outliers,
+            };
+        }
+
+        // 文本中的日期和日期时间
+        let is_date =
+            |v: &CellValue| matches!(v, CellValue::String(s) if parse_datetime(s).is_some());
+        if let Some(outliers) = matching(&is_date) {
+            let has_time = cells.iter().any(|(_, v)| {
+                matches!(v, CellValue::String(s) if parse_date(s).is_none() && parse_datetime(s).is_some())
+            });
+            return ColumnInference {
+                sql_type: if has_time {
+                    SqlType::DateTime
+                } else {
+                    SqlType::Date
+                },
+                outliers,
+            };
+        }
+
+        // 字符串：按字符数（而非字节数）决定使用 VARCHAR 还是 TEXT
+        let max_len = cells
+            .iter()
+            .map(|(_, v)| v.to_string().chars().count())
+            .max()
+            .unwrap_or(0);
+        let sql_type = if max_len <= 255 {
+            SqlType::Varchar((max_len + 50).min(255))
+        } else if max_len <= 1000 {
+            SqlType::Varchar(1000)
+        } else {
+            SqlType::Text
+        };
+        ColumnInference {
+            sql_type,
+            outliers: Vec::new(),
+        }
+    }
+
+    /// 把推断为布尔、日期或日期时间的列中的文本转换为标准形式，与推断的列类型一致
+    ///
+    /// `Yes`/`N` 等布尔文本转为布尔值，`2024.03.01`、`2024年03月01日` 等日期文本转为
+    /// ISO 8601 文本（`2024-03-01`、`2024-03-01 12:30:00`）；没有需要转换的列时不复制数据
+    pub fn normalize_values(data: &ExcelData) -> Cow<'_, ExcelData> {
+        let typed: Vec<(&String, SqlType)> = data
+            .headers
+            .iter()
+            .map(|header| (header, Self::infer_column_type(data, header)))
+            .filter(|(_, sql_type)| {
+                matches!(
+                    sql_type,
+                    SqlType::Boolean | SqlType::Date | SqlType::DateTime
+                )
+            })
+            .collect();
+        if typed.is_empty() {
+            return Cow::Borrowed(data);
+        }
+
+        let mut normalized = data.clone();
+        for row in &mut normalized.rows {
+            for (header, sql_type) in &typed {
+                let Some(CellValue::String(s)) = row.data.get(*header) else {
+                    continue;
+                };
+                let value = match sql_type {
+                    SqlType::Boolean => parse_bool(s).map(CellValue::Boolean),
+                    SqlType::Date => {
+                        parse_date(s).map(|d| CellValue::String(d.format("%Y-%m-%d").to_string()))
                     }
-                    CellValue::Empty => {}
+                    _ => parse_datetime(s)
+                        .map(|dt| CellValue::String(dt.format("%Y-%m-%d %H:%M:%S%.f").to_string())),
+                };
+                if let Some(value) = value {
+                    row.data.insert((*header).clone(), value);
                 }
             }
         }
+        Cow::Owned(normalized)
+    }
 
-        // 如果全是空值，默认为 VARCHAR
-        if all_empty {
-            return SqlType::Varchar(255);
-        }
+    /// 根据观察到的数值选择整数、DECIMAL 或 DOUBLE
+    ///
+    /// DECIMAL 的小数位数取观察到的最大值，整数部分在最大位数基础上预留 2 位
+    fn numeric_type(numbers: &[f64]) -> SqlType {
+        let scale = numbers
+            .iter()
+            .map(|n| {
+                let text = format!("{}", n.abs());
+                text.split_once('.').map_or(0, |(_, frac)| frac.len())
+            })
+            .max()
+            .unwrap_or(0);
+        let max_abs = numbers.iter().fold(0.0_f64, |acc, n| acc.max(n.abs()));
 
-        // 类型优先级判断
-        if has_boolean && !has_number && !has_string {
-            SqlType::Boolean
-        } else if has_number && !has_string {
-            if has_float {
-                SqlType::Double
+        if scale == 0 {
+            return if max_abs > i32::MAX as f64 {
+                SqlType::BigInt
             } else {
                 SqlType::Integer
-            }
-        } else if has_string {
-            // 根据最大长度决定使用 VARCHAR 还是 TEXT
-            if max_string_len <= 255 {
-                SqlType::Varchar((max_string_len + 50).min(255))
-            } else if max_string_len <= 1000 {
-                SqlType::Varchar(1000)
-            } else {
-                SqlType::Text
-            }
+            };
+        }
+
+        let int_digits = format!("{}", max_abs.trunc()).len();
+        let precision = int_digits + scale + 2;
+        if scale > MAX_DECIMAL_SCALE || precision > MAX_DECIMAL_PRECISION {
+            SqlType::Double
         } else {
-            SqlType::Varchar(255)
+            SqlType::Decimal(precision, scale)
         }
     }
 }
 
+/// 解析布尔文本（true/false、yes/no、y/n，不区分大小写）
+pub(crate) fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" => Some(true),
+        "false" | "no" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// 解析常见日期格式
+pub(crate) fn parse_date(s: &str) -> Option<NaiveDate> {
+    ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y年%m月%d日"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s.trim(), fmt).ok())
+}
+
+/// 解析常见日期时间格式，纯日期视为当天零点
+pub(crate) fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.fZ",
+        "%Y/%m/%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y/%m/%d %H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(s.trim(), fmt).ok())
+    .or_else(|| parse_date(s).and_then(|d| d.and_hms_opt(0, 0, 0)))
+}
+
+/// 列结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    /// 列名（应用标识符规范化后）
+    pub name: String,
+    /// Excel 中的原始列名
+    #[serde(default)]
+    pub source: String,
+    /// 推断的 SQL 类型
+    pub sql_type: SqlType,
+    /// 是否可为空
+    pub nullable: bool,
+    /// 数据中的值非空且互不重复，可作为唯一键的候选
+    #[serde(default)]
+    pub unique_candidate: bool,
+    /// 列注释
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 不符合推断类型的值：(数据行号，从 1 开始；原始值)
+    #[serde(default, skip_serializing)]
+    pub outliers: Vec<(usize, String)>,
+}
+
+impl ColumnSchema {
+    /// 创建原始列名与列名相同的列结构（用于测试）
+    #[cfg(test)]
+    pub fn new(name: &str, sql_type: SqlType, nullable: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            source: name.to_string(),
+            sql_type,
+            nullable,
+            unique_candidate: false,
+            comment: None,
+            outliers: Vec::new(),
+        }
+    }
+}
+
+/// 索引结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexSchema {
+    /// 索引名
+    pub name: String,
+    /// 索引包含的列
+    pub columns: Vec<String>,
+    /// 是否为唯一索引
+    pub unique: bool,
+}
+
+/// 表结构，由 `SchemaGenerator::infer` 从数据推断得到
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    /// 表名
+    pub name: String,
+    /// 列
+    pub columns: Vec<ColumnSchema>,
+    /// 主键列（可以为复合主键）
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+    /// 索引
+    #[serde(default)]
+    pub indexes: Vec<IndexSchema>,
+    /// 表注释
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl TableSchema {
+    /// 按列名查找列
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// 序列化为 JSON，用于保存表结构供以后对比
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从 `to_json` 保存的 JSON 读取表结构
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json.trim_start_matches('\u{FEFF}'))?)
+    }
+}
+
 /// CREATE TABLE 语句生成器
 pub struct SchemaGenerator {
     dialect: SqlDialect,
     table_name: String,
     add_if_not_exists: bool,
-    add_primary_key: Option<String>,
+    primary_keys: Vec<String>,
+    indexes: Vec<(Vec<String>, bool)>,
+    infer_not_null: bool,
+    type_tolerance: f64,
+    description_row: bool,
+    table_comment: Option<String>,
+    normalizer: Option<IdentifierNormalizer>,
+    qualified_table_name: bool,
 }
 
 impl SchemaGenerator {
@@ -167,7 +476,14 @@ impl SchemaGenerator {
             dialect,
             table_name,
             add_if_not_exists: true,
-            add_primary_key: None,
+            primary_keys: Vec::new(),
+            indexes: Vec::new(),
+            infer_not_null: true,
+            type_tolerance: 1.0,
+            description_row: false,
+            table_comment: None,
+            normalizer: None,
+            qualified_table_name: true,
         }
     }
 
@@ -178,73 +494,491 @@ impl SchemaGenerator {
     }
 
     /// 设置主键列
-    pub fn with_primary_key(mut self, column: String) -> Self {
-        self.add_primary_key = Some(column);
+    pub fn with_primary_key(self, column: String) -> Self {
+        self.with_primary_keys(vec![column])
+    }
+
+    /// 设置主键列，多列时生成 `PRIMARY KEY (a, b)` 表约束
+    pub fn with_primary_keys(mut self, columns: Vec<String>) -> Self {
+        self.primary_keys = columns;
+        self
+    }
+
+    /// 添加普通索引，每个索引可以包含多列
+    pub fn with_index(mut self, columns: Vec<String>) -> Self {
+        self.indexes.push((columns, false));
+        self
+    }
+
+    /// 添加唯一索引，每个索引可以包含多列
+    pub fn with_unique(mut self, columns: Vec<String>) -> Self {
+        self.indexes.push((columns, true));
+        self
+    }
+
+    /// 设置是否为没有空值的列推断 NOT NULL（默认开启）
+    pub fn with_not_null_inference(mut self, value: bool) -> Self {
+        self.infer_not_null = value;
+        self
+    }
+
+    /// 设置类型推断的容差：非空值中符合推断类型的最小比例（默认 1.0，即全部符合）
+    pub fn with_type_tolerance(mut self, tolerance: f64) -> Self {
+        self.type_tolerance = tolerance.clamp(0.0, 1.0);
+        self
+    }
+
+    /// 设置第一行数据是否为列说明（用作列注释，不参与类型推断）
+    pub fn with_description_row(mut self, value: bool) -> Self {
+        self.description_row = value;
+        self
+    }
+
+    /// 设置表注释
+    pub fn with_table_comment(mut self, comment: String) -> Self {
+        self.table_comment = Some(comment);
+        self
+    }
+
+    /// 设置标识符规范化（列名、表名的风格、保留字、长度限制和重命名）
+    pub fn with_normalizer(mut self, normalizer: IdentifierNormalizer) -> Self {
+        self.normalizer = Some(normalizer);
         self
     }
 
-    /// 生成 CREATE TABLE 语句
+    /// 设置表名是否按 `.` 解析为 `db.schema.table` 形式的限定名（默认开启）
+    ///
+    /// 表名来自工作表名（如 `Q1.2024`）时应关闭，整个名称作为一个标识符引用
+    pub fn with_qualified_table_name(mut self, value: bool) -> Self {
+        self.qualified_table_name = value;
+        self
+    }
+
+    /// 按限定名设置引用表名，所有语句都通过它引用表名
+    pub fn quote_table(&self, name: &str) -> String {
+        self.table_parts(name)
+            .iter()
+            .map(|part| self.dialect.quote_identifier(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// 表名的各段，不解析限定名时只有一段
+    fn table_parts<'a>(&self, name: &'a str) -> Vec<&'a str> {
+        if self.qualified_table_name {
+            name.split('.').collect()
+        } else {
+            vec![name]
+        }
+    }
+
+    /// 生成 CREATE TABLE 语句，以及注释和 CREATE INDEX 语句
     pub fn generate(&self, data: &ExcelData) -> Result<String> {
+        let schema = self.infer(data)?;
+        let mut statements = vec![self.create_table_sql(&schema)];
+        statements.extend(self.create_index_sqls(&schema));
+        Ok(statements.join("\n"))
+    }
+
+    /// 从数据推断表结构
+    pub fn infer(&self, data: &ExcelData) -> Result<TableSchema> {
+        // 说明行不参与类型推断
+        let (descriptions, body) = match data.rows.split_first() {
+            Some((first, rest)) if self.description_row => (
+                Some(first),
+                ExcelData {
+                    sheet_name: data.sheet_name.clone(),
+                    headers: data.headers.clone(),
+                    rows: rest.to_vec(),
+                },
+            ),
+            _ => (None, data.clone()),
+        };
+
+        // 规范化表名和列名；主键和索引列可以使用原列名或规范化后的列名
+        let (table_name, names) = match &self.normalizer {
+            Some(normalizer) => (
+                normalizer.normalize_table(&self.table_name),
+                normalizer.normalize_all(&data.headers)?,
+            ),
+            None => (self.table_name.clone(), data.headers.clone()),
+        };
+        check_identifier("表名", &table_name)?;
+        for name in &names {
+            check_identifier("列名", name)?;
+        }
+        let resolve = |column: &String| -> Result<usize> {
+            data.headers
+                .iter()
+                .position(|h| h == column)
+                .or_else(|| names.iter().position(|n| n == column))
+                .ok_or_else(|| ExcelCliError::ExportError(format!("列 '{}' 不存在", column)))
+        };
+        let pk_indices = self
+            .primary_keys
+            .iter()
+            .map(resolve)
+            .collect::<Result<Vec<_>>>()?;
+
+        let columns = data
+            .headers
+            .iter()
+            .zip(&names)
+            .enumerate()
+            .map(|(i, (header, name))| {
+                let inference = TypeInference::infer_column(&body, header, self.type_tolerance);
+                let sql_type = inference.sql_type;
+                let values: Vec<&CellValue> = body
+                    .rows
+                    .iter()
+                    .map(|row| row.data.get(header).unwrap_or(&CellValue::Empty))
+                    .collect();
+                let has_empty = values.iter().any(|v| v.is_empty());
+                let nullable = !pk_indices.contains(&i)
+                    && (has_empty || values.is_empty() || !self.infer_not_null);
+                let distinct: HashSet<String> = values.iter().map(|v| v.to_string()).collect();
+                let unique_candidate = values.len() >= 2
+                    && !has_empty
+                    && distinct.len() == values.len()
+                    && !matches!(
+                        sql_type,
+                        SqlType::Boolean | SqlType::Float | SqlType::Double
+                    );
+                let comment = descriptions
+                    .and_then(|row| row.data.get(header))
+                    .map(|v| v.to_string().trim().to_string())
+                    .filter(|c| !c.is_empty());
+
+                ColumnSchema {
+                    name: name.clone(),
+                    source: header.clone(),
+                    sql_type,
+                    nullable,
+                    unique_candidate,
+                    comment,
+                    // 行号按数据行计算，包含说明行
+                    outliers: inference
+                        .outliers
+                        .into_iter()
+                        .map(|(row, value)| (row + descriptions.is_some() as usize, value))
+                        .collect(),
+                }
+            })
+            .collect::<Vec<ColumnSchema>>();
+        if !self.dialect.supports_nul() {
+            let commented = columns.iter().find(|c| {
+                c.comment
+                    .as_deref()
+                    .is_some_and(|comment| comment.contains('\0'))
+            });
+            if let Some(column) = commented {
+                return Err(ExcelCliError::ExportError(format!(
+                    "列 '{}' 的注释包含 NUL 字符，{:?} 的字符串无法表示",
+                    column.source, self.dialect
+                )));
+            }
+        }
+
+        let table_short = self.table_parts(&table_name).pop().unwrap_or(&table_name);
+        // 索引名按标识符长度限制截断，截断后重名时追加序号
+        let index_names = self
+            .normalizer
+            .clone()
+            .unwrap_or_else(|| IdentifierNormalizer::new(self.dialect));
+        let mut used_index_names = HashSet::new();
+        let mut indexes = Vec::new();
+        for (index_columns, unique) in &self.indexes {
+            if matches!(
+                self.dialect,
+                SqlDialect::ClickHouse | SqlDialect::Snowflake | SqlDialect::BigQuery
+            ) {
+                return Err(ExcelCliError::ExportError(format!(
+                    "{:?} 不支持 CREATE INDEX",
+                    self.dialect
+                )));
+            }
+
+            let indices = index_columns
+                .iter()
+                .map(resolve)
+                .collect::<Result<Vec<_>>>()?;
+            if *unique {
+                // 数据中已有重复值时，创建唯一索引必然失败
+                let mut seen = HashSet::new();
+                for row in &body.rows {
+                    let key: Vec<String> = indices
+                        .iter()
+                        .map(|&i| {
+                            row.data
+                                .get(&data.headers[i])
+                                .map_or(String::new(), |v| v.to_string())
+                        })
+                        .collect();
+                    if !seen.insert(key.clone()) {
+                        return Err(ExcelCliError::ExportError(format!(
+                            "唯一索引列 ({}) 存在重复值: {}",
+                            index_columns.join(", "),
+                            key.join(", ")
+                        )));
+                    }
+                }
+            }
+
+            let columns: Vec<String> = indices.iter().map(|&i| names[i].clone()).collect();
+            let name = format!(
+                "{}_{}_{}",
+                if *unique { "uq" } else { "idx" },
+                table_short,
+                columns.join("_")
+            );
+            indexes.push(IndexSchema {
+                name: index_names.fit_name(&name, &mut used_index_names),
+                columns,
+                unique: *unique,
+            });
+        }
+
+        Ok(TableSchema {
+            name: table_name,
+            columns,
+            primary_key: pk_indices.iter().map(|&i| names[i].clone()).collect(),
+            indexes,
+            comment: self.table_comment.clone(),
+        })
+    }
+
+    /// 生成 CREATE TABLE 语句和表、列注释语句
+    pub fn create_table_sql(&self, schema: &TableSchema) -> String {
+        let dialect = self.dialect;
+        let quoted_table = self.quote_table(&schema.name);
         let mut sql = String::new();
 
         // CREATE TABLE 语句开头
         if self.add_if_not_exists {
-            match self.dialect {
-                SqlDialect::SQLite | SqlDialect::PostgreSQL | SqlDialect::MySQL => {
-                    sql.push_str(&format!(
-                        "CREATE TABLE IF NOT EXISTS {} (\n",
-                        self.dialect.quote_identifier(&self.table_name)
-                    ));
+            match dialect {
+                SqlDialect::SQLite
+                | SqlDialect::PostgreSQL
+                | SqlDialect::MySQL
+                | SqlDialect::ClickHouse
+                | SqlDialect::DuckDB
+                | SqlDialect::Snowflake
+                | SqlDialect::BigQuery => {
+                    sql.push_str(&format!("CREATE TABLE IF NOT EXISTS {} (\n", quoted_table));
                 }
                 SqlDialect::SqlServer => {
                     sql.push_str(&format!(
-                        "IF NOT EXISTS (SELECT * FROM sys.tables WHERE name = '{}')\n",
-                        self.table_name
-                    ));
-                    sql.push_str(&format!(
-                        "CREATE TABLE {} (\n",
-                        self.dialect.quote_identifier(&self.table_name)
+                        "IF OBJECT_ID({}, N'U') IS NULL\n",
+                        dialect.quote_string(&quoted_table)
                     ));
+                    sql.push_str(&format!("CREATE TABLE {} (\n", quoted_table));
                 }
                 SqlDialect::Oracle => {
-                    sql.push_str(&format!(
-                        "CREATE TABLE {} (\n",
-                        self.dialect
-                            .quote_identifier(&self.table_name.to_uppercase())
-                    ));
+                    sql.push_str(&format!("CREATE TABLE {} (\n", quoted_table));
                 }
             }
         } else {
-            sql.push_str(&format!(
-                "CREATE TABLE {} (\n",
-                self.dialect.quote_identifier(&self.table_name)
-            ));
+            sql.push_str(&format!("CREATE TABLE {} (\n", quoted_table));
         }
 
+        // 单列主键内联声明（ClickHouse 通过排序键、BigQuery 通过表约束声明）
+        let inline_pk = match schema.primary_key.as_slice() {
+            [pk] if !matches!(dialect, SqlDialect::ClickHouse | SqlDialect::BigQuery) => {
+                Some(pk.as_str())
+            }
+            _ => None,
+        };
+        let quoted_pk: Vec<String> = schema
+            .primary_key
+            .iter()
+            .map(|c| dialect.quote_identifier(c))
+            .collect();
+
         // 添加列定义
         let mut column_defs = Vec::new();
+        for column in &schema.columns {
+            let mut type_str = column.sql_type.to_sql_string(&dialect);
 
-        for column in &data.headers {
-            let sql_type = TypeInference::infer_column_type(data, column);
-            let type_str = sql_type.to_sql_string(&self.dialect);
-            let quoted_col = self.dialect.quote_identifier(column);
+            // ClickHouse 的列默认不可为空，可为空的列使用 Nullable
+            if dialect == SqlDialect::ClickHouse && column.nullable {
+                type_str = format!("Nullable({})", type_str);
+            }
 
-            let mut def = format!("    {} {}", quoted_col, type_str);
+            let mut def = format!(
+                "    {} {}",
+                dialect.quote_identifier(&column.name),
+                type_str
+            );
+            if inline_pk == Some(column.name.as_str()) {
+                def.push_str(" PRIMARY KEY");
+            } else if !column.nullable && dialect != SqlDialect::ClickHouse {
+                def.push_str(" NOT NULL");
+            }
 
-            // 如果是主键列
-            if let Some(ref pk_col) = self.add_primary_key {
-                if pk_col == column {
-                    def.push_str(" PRIMARY KEY");
+            // MySQL、ClickHouse、BigQuery 的列注释写在列定义中
+            if let Some(comment) = &column.comment {
+                match dialect {
+                    SqlDialect::MySQL | SqlDialect::ClickHouse => {
+                        def.push_str(&format!(" COMMENT {}", dialect.quote_string(comment)));
+                    }
+                    SqlDialect::BigQuery => def.push_str(&format!(
+                        " OPTIONS(description = {})",
+                        dialect.quote_string(comment)
+                    )),
+                    _ => {}
                 }
             }
 
             column_defs.push(def);
         }
 
+        if inline_pk.is_none() && !quoted_pk.is_empty() {
+            match dialect {
+                SqlDialect::ClickHouse => {}
+                SqlDialect::BigQuery => column_defs.push(format!(
+                    "    PRIMARY KEY ({}) NOT ENFORCED",
+                    quoted_pk.join(", ")
+                )),
+                _ => column_defs.push(format!("    PRIMARY KEY ({})", quoted_pk.join(", "))),
+            }
+        }
+
         sql.push_str(&column_defs.join(",\n"));
-        sql.push_str("\n);");
+        sql.push_str("\n)");
+
+        // ClickHouse 需要表引擎；有主键时使用 ReplacingMergeTree，配合 UPSERT 生成的 INSERT 去重
+        if dialect == SqlDialect::ClickHouse {
+            if quoted_pk.is_empty() {
+                sql.push_str("\nENGINE = MergeTree\nORDER BY tuple()");
+            } else {
+                sql.push_str(&format!(
+                    "\nENGINE = ReplacingMergeTree\nORDER BY ({})",
+                    quoted_pk.join(", ")
+                ));
+            }
+        }
+
+        // 表注释：MySQL、ClickHouse、BigQuery 写在建表语句中，其他方言使用单独的语句
+        let mut comment_sqls = Vec::new();
+        if let Some(comment) = &schema.comment {
+            let literal = dialect.quote_string(comment);
+            match dialect {
+                SqlDialect::MySQL => sql.push_str(&format!("\nCOMMENT = {}", literal)),
+                SqlDialect::ClickHouse => sql.push_str(&format!("\nCOMMENT {}", literal)),
+                SqlDialect::BigQuery => {
+                    sql.push_str(&format!("\nOPTIONS(description = {})", literal))
+                }
+                SqlDialect::SqlServer => {
+                    comment_sqls.push(self.sqlserver_description(&schema.name, None, comment))
+                }
+                SqlDialect::SQLite => {}
+                _ => {
+                    comment_sqls.push(format!("COMMENT ON TABLE {} IS {};", quoted_table, literal))
+                }
+            }
+        }
+        sql.push(';');
+
+        for column in &schema.columns {
+            let Some(comment) = &column.comment else {
+                continue;
+            };
+            match dialect {
+                SqlDialect::PostgreSQL
+                | SqlDialect::Oracle
+                | SqlDialect::DuckDB
+                | SqlDialect::Snowflake => comment_sqls.push(format!(
+                    "COMMENT ON COLUMN {}.{} IS {};",
+                    quoted_table,
+                    dialect.quote_identifier(&column.name),
+                    dialect.quote_string(comment)
+                )),
+                SqlDialect::SqlServer => comment_sqls.push(self.sqlserver_description(
+                    &schema.name,
+                    Some(&column.name),
+                    comment,
+                )),
+                _ => {}
+            }
+        }
+
+        for comment_sql in comment_sqls {
+            sql.push('\n');
+            sql.push_str(&comment_sql);
+        }
+        sql
+    }
 
-        Ok(sql)
+    /// 生成 CREATE INDEX 语句
+    pub fn create_index_sqls(&self, schema: &TableSchema) -> Vec<String> {
+        let dialect = self.dialect;
+        let if_not_exists = self.add_if_not_exists
+            && matches!(
+                dialect,
+                SqlDialect::PostgreSQL | SqlDialect::SQLite | SqlDialect::DuckDB
+            );
+
+        schema
+            .indexes
+            .iter()
+            .map(|index| {
+                let columns: Vec<String> = index
+                    .columns
+                    .iter()
+                    .map(|c| dialect.quote_identifier(c))
+                    .collect();
+                format!(
+                    "CREATE {}INDEX {}{} ON {} ({});",
+                    if index.unique { "UNIQUE " } else { "" },
+                    if if_not_exists { "IF NOT EXISTS " } else { "" },
+                    dialect.quote_identifier(&index.name),
+                    self.quote_table(&schema.name),
+                    columns.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    /// 生成与 `create_table_sql` 对应的 DROP TABLE 语句，索引和注释随表一起删除
+    pub fn drop_table_sql(&self, schema: &TableSchema) -> String {
+        let quoted_table = self.quote_table(&schema.name);
+        if !self.add_if_not_exists {
+            return format!("DROP TABLE {};", quoted_table);
+        }
+        match self.dialect {
+            SqlDialect::SqlServer => format!(
+                "IF OBJECT_ID({}, N'U') IS NOT NULL\nDROP TABLE {};",
+                self.dialect.quote_string(&quoted_table),
+                quoted_table
+            ),
+            SqlDialect::Oracle => format!("DROP TABLE {};", quoted_table),
+            _ => format!("DROP TABLE IF EXISTS {};", quoted_table),
+        }
+    }
+
+    /// SQL Server 通过扩展属性 MS_Description 保存注释
+    fn sqlserver_description(&self, table: &str, column: Option<&str>, comment: &str) -> String {
+        let dialect = self.dialect;
+        let parts = self.table_parts(table);
+        let (schema_name, table_name) = match parts.as_slice() {
+            [.., schema_name, table_name] => (*schema_name, *table_name),
+            _ => ("dbo", table),
+        };
+
+        let mut sql = format!(
+            "EXEC sp_addextendedproperty @name = N'MS_Description', @value = {}, \
+             @level0type = N'SCHEMA', @level0name = {}, @level1type = N'TABLE', @level1name = {}",
+            dialect.quote_string(comment),
+            dialect.quote_string(schema_name),
+            dialect.quote_string(table_name)
+        );
+        if let Some(column) = column {
+            sql.push_str(&format!(
+                ", @level2type = N'COLUMN', @level2name = {}",
+                dialect.quote_string(column)
+            ));
+        }
+        sql.push(';');
+        sql
     }
 }
 
@@ -281,6 +1015,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_smarter_type_inference() {
+        let column = |values: Vec<CellValue>| {
+            let rows: Vec<&[CellValue]> = values.iter().map(std::slice::from_ref).collect();
+            ExcelData::from_rows("Sheet1", &["c"], &rows)
+        };
+        let text = CellValue::text;
+
+        let money = column(vec![CellValue::Number(1234.5), CellValue::Number(0.25)]);
+        assert_eq!(
+            TypeInference::infer_column_type(&money, "c"),
+            SqlType::Decimal(8, 2)
+        );
+        assert_eq!(
+            SqlType::Decimal(8, 2).to_sql_string(&SqlDialect::PostgreSQL),
+            "NUMERIC(8, 2)"
+        );
+        let noisy = column(vec![CellValue::Number(0.1 + 0.2)]);
+        assert_eq!(
+            TypeInference::infer_column_type(&noisy, "c"),
+            SqlType::Double
+        );
+
+        let flags = column(vec![text("Yes"), text("n"), CellValue::Boolean(true)]);
+        assert_eq!(
+            TypeInference::infer_column_type(&flags, "c"),
+            SqlType::Boolean
+        );
+        let dates = column(vec![text("2024-03-01"), text("2024/03/02")]);
+        assert_eq!(TypeInference::infer_column_type(&dates, "c"), SqlType::Date);
+        let times = column(vec![text("2024-03-01"), text("2024-03-01 08:30:00")]);
+        assert_eq!(
+            TypeInference::infer_column_type(&times, "c"),
+            SqlType::DateTime
+        );
+
+        // 写入数据库前把文本转换为与推断类型一致的标准形式
+        let flags = TypeInference::normalize_values(&flags);
+        assert!(matches!(flags.rows[0].data["c"], CellValue::Boolean(true)));
+        assert!(matches!(flags.rows[1].data["c"], CellValue::Boolean(false)));
+        let dates = column(vec![text("2024.03.01"), text("2024年03月02日")]);
+        let dates = TypeInference::normalize_values(&dates);
+        assert_eq!(dates.rows[1].data["c"].to_string(), "2024-03-02");
+        let times = TypeInference::normalize_values(&times);
+        assert_eq!(times.rows[0].data["c"].to_string(), "2024-03-01 00:00:00");
+        assert!(matches!(
+            TypeInference::normalize_values(&column(vec![text("a")])),
+            Cow::Borrowed(_)
+        ));
+
+        // 按字符数计算长度：4 个汉字是 4 个字符而不是 12 个字节
+        let cjk = column(vec![text("客户名称")]);
+        assert_eq!(
+            TypeInference::infer_column_type(&cjk, "c"),
+            SqlType::Varchar(54)
+        );
+
+        // 容差：3/4 为数值时，严格模式为字符串，0.75 容差下为整数并报告异常值
+        let mostly = column(vec![
+            CellValue::Number(1.0),
+            CellValue::Number(2.0),
+            text("N/A"),
+            CellValue::Number(3.0),
+        ]);
+        assert_eq!(
+            TypeInference::infer_column_type(&mostly, "c"),
+            SqlType::Varchar(53)
+        );
+        let inference = TypeInference::infer_column(&mostly, "c", 0.75);
+        assert_eq!(inference.sql_type, SqlType::Integer);
+        assert_eq!(inference.outliers, vec![(3, "N/A".to_string())]);
+    }
+
     #[test]
     fn test_schema_generation() {
         let mut data = ExcelData::new(
@@ -302,4 +1109,171 @@ mod tests {
         assert!(sql.contains("`id`"));
         assert!(sql.contains("PRIMARY KEY"));
     }
+
+    #[test]
+    fn test_warehouse_schema() {
+        let mut data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["id".to_string(), "name".to_string()],
+        );
+
+        let mut row1 = HashMap::new();
+        row1.insert("id".to_string(), CellValue::Number(1.0));
+        row1.insert("name".to_string(), CellValue::String("Test".to_string()));
+        data.add_row(ExcelRow { data: row1 });
+
+        let mut row2 = HashMap::new();
+        row2.insert("id".to_string(), CellValue::Number(2.0));
+        data.add_row(ExcelRow { data: row2 });
+
+        let sql = SchemaGenerator::new(SqlDialect::ClickHouse, "users".to_string())
+            .with_primary_key("id".to_string())
+            .generate(&data)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE IF NOT EXISTS `users` (\n    `id` Int32,\n    `name` Nullable(String)\n)\nENGINE = ReplacingMergeTree\nORDER BY (`id`);"
+        );
+
+        let sql = SchemaGenerator::new(SqlDialect::BigQuery, "users".to_string())
+            .with_primary_key("id".to_string())
+            .generate(&data)
+            .unwrap();
+        assert!(sql.contains(
+            "`id` INT64 NOT NULL,\n    `name` STRING,\n    PRIMARY KEY (`id`) NOT ENFORCED\n);"
+        ));
+    }
+
+    #[test]
+    fn test_constraints_indexes_and_comments() {
+        let headers = vec!["id".to_string(), "region".to_string(), "email".to_string()];
+        let mut data = ExcelData::new("Sheet1".to_string(), headers);
+        for (id, region, email) in [
+            (0.0, "区域", "邮箱地址"),
+            (1.0, "cn", "a@x.com"),
+            (2.0, "us", "b@x.com"),
+            (2.0, "cn", ""),
+        ] {
+            let mut row = HashMap::new();
+            row.insert("id".to_string(), CellValue::Number(id));
+            row.insert("region".to_string(), CellValue::String(region.to_string()));
+            let email = if email.is_empty() {
+                CellValue::Empty
+            } else {
+                CellValue::String(email.to_string())
+            };
+            row.insert("email".to_string(), email);
+            data.add_row(ExcelRow { data: row });
+        }
+        // 第一行为列说明
+        data.rows[0]
+            .data
+            .insert("id".to_string(), CellValue::String("编号".to_string()));
+
+        let generator = SchemaGenerator::new(SqlDialect::PostgreSQL, "users".to_string())
+            .with_primary_keys(vec!["id".to_string(), "region".to_string()])
+            .with_index(vec!["email".to_string()])
+            .with_description_row(true)
+            .with_table_comment("用户".to_string());
+        let schema = generator.infer(&data).unwrap();
+        assert!(schema.column("email").unwrap().nullable);
+        assert!(!schema.column("id").unwrap().unique_candidate);
+        assert_eq!(
+            schema.column("region").unwrap().comment.as_deref(),
+            Some("区域")
+        );
+
+        let sql = generator.generate(&data).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE IF NOT EXISTS \"users\" (\n    \"id\" INTEGER NOT NULL,\n    \
+             \"region\" VARCHAR(52) NOT NULL,\n    \"email\" VARCHAR(57),\n    \
+             PRIMARY KEY (\"id\", \"region\")\n);\n\
+             COMMENT ON TABLE \"users\" IS '用户';\n\
+             COMMENT ON COLUMN \"users\".\"id\" IS '编号';\n\
+             COMMENT ON COLUMN \"users\".\"region\" IS '区域';\n\
+             COMMENT ON COLUMN \"users\".\"email\" IS '邮箱地址';\n\
+             CREATE INDEX IF NOT EXISTS \"idx_users_email\" ON \"users\" (\"email\");"
+        );
+
+        // MySQL 的注释写在建表语句中；数据中有重复值时不能创建唯一索引
+        let sql = SchemaGenerator::new(SqlDialect::MySQL, "users".to_string())
+            .with_description_row(true)
+            .with_table_comment("用户".to_string())
+            .generate(&data)
+            .unwrap();
+        assert!(sql.contains("`region` VARCHAR(52) NOT NULL COMMENT '区域',"));
+        assert!(sql.ends_with("\n)\nCOMMENT = '用户';"));
+        let result = SchemaGenerator::new(SqlDialect::MySQL, "users".to_string())
+            .with_unique(vec!["id".to_string()])
+            .generate(&data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sqlserver_if_not_exists_escaping() {
+        let data = ExcelData::new("Sheet1".to_string(), vec!["id".to_string()]);
+        let generator = SchemaGenerator::new(SqlDialect::SqlServer, "dbo.o'brien]".to_string());
+        let sql = generator.generate(&data).unwrap();
+        assert!(sql.starts_with(
+            "IF OBJECT_ID('[dbo].[o''brien]]]', N'U') IS NULL\nCREATE TABLE [dbo].[o'brien]]] ("
+        ));
+        let schema = generator.infer(&data).unwrap();
+        assert_eq!(
+            generator.drop_table_sql(&schema),
+            "IF OBJECT_ID('[dbo].[o''brien]]]', N'U') IS NOT NULL\nDROP TABLE [dbo].[o'brien]]];"
+        );
+    }
+    #[test]
+    fn test_normalized_schema() {
+        let data = ExcelData::new(
+            "Sheet1".to_string(),
+            vec!["Order ID".to_string(), "Date".to_string()],
+        );
+        let normalizer = IdentifierNormalizer::new(SqlDialect::Oracle)
+            .with_case(crate::identifier::IdentifierCase::Upper);
+        let sql = SchemaGenerator::new(SqlDialect::Oracle, "Sales Orders".to_string())
+            .with_if_not_exists(false)
+            .with_primary_key("Order ID".to_string())
+            .with_normalizer(normalizer)
+            .generate(&data)
+            .unwrap();
+        assert!(sql.starts_with("CREATE TABLE \"SALES_ORDERS\" (\n    \"ORDER_ID\" "));
+        assert!(sql.contains("PRIMARY KEY"));
+        assert!(sql.contains("\"DATE_\""));
+    }
+
+    #[test]
+    fn test_long_index_names_stay_unique() {
+        let data = ExcelData::from_rows(
+            "Sheet1",
+            &[
+                "customer_region_code_primary",
+                "customer_region_code_secondary",
+                "客户所在城市名称",
+            ],
+            &[&[
+                CellValue::text("N"),
+                CellValue::text("North"),
+                CellValue::text("北京"),
+            ]],
+        );
+        let schema = SchemaGenerator::new(SqlDialect::Oracle, "orders".to_string())
+            .with_index(vec!["customer_region_code_primary".to_string()])
+            .with_index(vec!["customer_region_code_secondary".to_string()])
+            .with_index(vec!["客户所在城市名称".to_string()])
+            .infer(&data)
+            .unwrap();
+        let names: Vec<&str> = schema.indexes.iter().map(|i| i.name.as_str()).collect();
+        // 截断后相同的名称追加序号，多字节字符不被拆开
+        assert_eq!(
+            names,
+            vec![
+                "idx_orders_customer_region_cod",
+                "idx_orders_customer_region_c_2",
+                "idx_orders_客户所在城市"
+            ]
+        );
+        assert!(names.iter().all(|n| n.len() <= 30));
+    }
 }
@@ -0,0 +1,777 @@
+use crate::error::{ExcelCliError, Result};
+use crate::exporter::sql::SqlDialect;
+use crate::schema::{ColumnSchema, SqlType, TableSchema};
+
+/// 修改已有列的类型或可空性
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnAlteration {
+    /// 列名
+    pub name: String,
+    /// 原类型
+    pub from: SqlType,
+    /// 新类型（只放宽可空性时与原类型相同）
+    pub to: SqlType,
+    /// 修改后是否可为空
+    pub nullable: bool,
+    /// 是否修改类型
+    pub type_changed: bool,
+    /// 是否去掉 NOT NULL 约束
+    pub drop_not_null: bool,
+    /// 类型收窄，转换时可能丢失数据
+    pub lossy: bool,
+}
+
+/// 表结构变更
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// 新增列（已有行没有值，总是允许为空）
+    AddColumn(ColumnSchema),
+    /// 删除列
+    DropColumn(String),
+    /// 修改列
+    AlterColumn(ColumnAlteration),
+}
+
+/// 新旧表结构的差异，可生成对应方言的 ALTER TABLE 语句
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDiff {
+    dialect: SqlDialect,
+    /// 要修改的表（旧表结构中的表名）
+    pub table: String,
+    /// 变更列表：新增列、修改列、删除列
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// 对比旧表结构和从新数据推断的表结构，列名不区分大小写
+    pub fn compare(old: &TableSchema, new: &TableSchema, dialect: SqlDialect) -> Self {
+        let find = |schema: &TableSchema, name: &str| {
+            schema
+                .columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(name))
+                .cloned()
+        };
+
+        let mut changes: Vec<SchemaChange> = new
+            .columns
+            .iter()
+            .filter(|c| find(old, &c.name).is_none())
+            .map(|c| {
+                SchemaChange::AddColumn(ColumnSchema {
+                    nullable: true,
+                    ..c.clone()
+                })
+            })
+            .collect();
+
+        for old_column in &old.columns {
+            let Some(new_column) = find(new, &old_column.name) else {
+                changes.push(SchemaChange::DropColumn(old_column.name.clone()));
+                continue;
+            };
+
+            let from = &old_column.sql_type;
+            // 同类类型合并为能同时容纳新旧数据的类型，避免 VARCHAR 长度等随数据来回收窄
+            let to = if type_family(from) == type_family(&new_column.sql_type) {
+                widen(from, &new_column.sql_type)
+            } else {
+                new_column.sql_type.clone()
+            };
+            let type_changed = from.to_sql_string(&dialect) != to.to_sql_string(&dialect);
+            // 只放宽 NOT NULL：新数据出现空值时才需要修改，主键列保持不变
+            let in_primary_key = old
+                .primary_key
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(&old_column.name));
+            let drop_not_null = !old_column.nullable && new_column.nullable && !in_primary_key;
+
+            if type_changed || drop_not_null {
+                changes.push(SchemaChange::AlterColumn(ColumnAlteration {
+                    name: old_column.name.clone(),
+                    from: from.clone(),
+                    to: if type_changed {
+                        to.clone()
+                    } else {
+                        from.clone()
+                    },
+                    nullable: old_column.nullable || drop_not_null,
+                    type_changed,
+                    drop_not_null,
+                    lossy: type_changed && !is_lossless(from, &to),
+                }));
+            }
+        }
+
+        Self {
+            dialect,
+            table: old.name.clone(),
+            changes,
+        }
+    }
+
+    /// 是否没有任何变更
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// 可能丢失数据的类型收窄
+    pub fn lossy_changes(&self) -> Vec<&ColumnAlteration> {
+        self.changes
+            .iter()
+            .filter_map(|change| match change {
+                SchemaChange::AlterColumn(alteration) if alteration.lossy => Some(alteration),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 新数据中已不存在、删除会丢失数据的列
+    pub fn dropped_columns(&self) -> Vec<&str> {
+        self.changes
+            .iter()
+            .filter_map(|change| match change {
+                SchemaChange::DropColumn(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 生成 ALTER TABLE 语句
+    ///
+    /// 类型收窄和删除列默认以注释形式输出并附带警告，`allow_lossy` 为 true 时才输出可执行的语句
+    pub fn alter_sqls(&self, allow_lossy: bool) -> Vec<String> {
+        let dialect = self.dialect;
+        let table = dialect.quote_table_name(&self.table);
+        let mut statements = Vec::new();
+
+        for change in &self.changes {
+            match change {
+                SchemaChange::AddColumn(column) => {
+                    let column_def = format!(
+                        "{} {}",
+                        dialect.quote_identifier(&column.name),
+                        column_type(dialect, &column.sql_type, true)
+                    );
+                    statements.push(match dialect {
+                        SqlDialect::SqlServer => {
+                            format!("ALTER TABLE {} ADD {};", table, column_def)
+                        }
+                        SqlDialect::Oracle => {
+                            format!("ALTER TABLE {} ADD ({});", table, column_def)
+                        }
+                        _ => format!("ALTER TABLE {} ADD COLUMN {};", table, column_def),
+                    });
+                }
+                SchemaChange::DropColumn(name) => {
+                    let sql = format!(
+                        "ALTER TABLE {} DROP COLUMN {};",
+                        table,
+                        dialect.quote_identifier(name)
+                    );
+                    statements.push(format!("-- ⚠️ 删除列会丢失数据: {}", name));
+                    statements.push(if allow_lossy {
+                        sql
+                    } else {
+                        format!("-- {}", sql)
+                    });
+                }
+                SchemaChange::AlterColumn(alteration) => {
+                    let sqls = self.alter_column_sqls(&table, alteration);
+                    if alteration.lossy {
+                        statements.push(format!(
+                            "-- ⚠️ 类型收窄可能丢失数据: {} {} -> {}",
+                            alteration.name,
+                            alteration.from.to_sql_string(&dialect),
+                            alteration.to.to_sql_string(&dialect)
+                        ));
+                        if !allow_lossy {
+                            statements.extend(sqls.into_iter().map(|sql| format!("-- {}", sql)));
+                            continue;
+                        }
+                    }
+                    statements.extend(sqls);
+                }
+            }
+        }
+        statements
+    }
+
+    /// 修改单列的语句，不同方言的语法差异较大
+    fn alter_column_sqls(&self, table: &str, alteration: &ColumnAlteration) -> Vec<String> {
+        let dialect = self.dialect;
+        let column = dialect.quote_identifier(&alteration.name);
+        let sql_type = alteration.to.to_sql_string(&dialect);
+        let mut sqls = Vec::new();
+
+        match dialect {
+            // MODIFY 会重新定义整列，需要同时给出可空性
+            SqlDialect::MySQL => sqls.push(format!(
+                "ALTER TABLE {} MODIFY COLUMN {} {}{};",
+                table,
+                column,
+                sql_type,
+                if alteration.nullable { "" } else { " NOT NULL" }
+            )),
+            SqlDialect::SqlServer => sqls.push(format!(
+                "ALTER TABLE {} ALTER COLUMN {} {} {};",
+                table,
+                column,
+                sql_type,
+                if alteration.nullable {
+                    "NULL"
+                } else {
+                    "NOT NULL"
+                }
+            )),
+            SqlDialect::Oracle => {
+                let mut definition = column;
+                if alteration.type_changed {
+                    definition.push_str(&format!(" {}", sql_type));
+                }
+                if alteration.drop_not_null {
+                    definition.push_str(" NULL");
+                }
+                sqls.push(format!("ALTER TABLE {} MODIFY ({});", table, definition));
+            }
+            SqlDialect::ClickHouse => sqls.push(format!(
+                "ALTER TABLE {} MODIFY COLUMN {} {};",
+                table,
+                column,
+                column_type(dialect, &alteration.to, alteration.nullable)
+            )),
+            // SQLite 不能修改列定义，只能重建表
+            SqlDialect::SQLite => sqls.push(format!(
+                "-- SQLite 不支持修改列定义，需要重建表: {} {}{}",
+                alteration.name,
+                sql_type,
+                if alteration.nullable { "" } else { " NOT NULL" }
+            )),
+            SqlDialect::PostgreSQL
+            | SqlDialect::DuckDB
+            | SqlDialect::Snowflake
+            | SqlDialect::BigQuery => {
+                if alteration.type_changed {
+                    // PostgreSQL 跨类别转换（如文本转整数）需要 USING 子句
+                    let using = if dialect == SqlDialect::PostgreSQL
+                        && type_family(&alteration.from) != type_family(&alteration.to)
+                    {
+                        format!(" USING {}::{}", column, sql_type)
+                    } else {
+                        String::new()
+                    };
+                    let keyword = if dialect == SqlDialect::PostgreSQL {
+                        "TYPE"
+                    } else {
+                        "SET DATA TYPE"
+                    };
+                    sqls.push(format!(
+                        "ALTER TABLE {} ALTER COLUMN {} {} {}{};",
+                        table, column, keyword, sql_type, using
+                    ));
+                }
+                if alteration.drop_not_null {
+                    sqls.push(format!(
+                        "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;",
+                        table, column
+                    ));
+                }
+            }
+        }
+        sqls
+    }
+}
+
+/// 列类型，ClickHouse 可为空的列使用 `Nullable(...)`
+fn column_type(dialect: SqlDialect, sql_type: &SqlType, nullable: bool) -> String {
+    let sql_type = sql_type.to_sql_string(&dialect);
+    if dialect == SqlDialect::ClickHouse && nullable {
+        format!("Nullable({})", sql_type)
+    } else {
+        sql_type
+    }
+}
+
+/// 类型类别：布尔、数值、字符串、日期
+fn type_family(sql_type: &SqlType) -> u8 {
+    match sql_type {
+        SqlType::Boolean => 0,
+        SqlType::Integer
+        | SqlType::BigInt
+        | SqlType::Float
+        | SqlType::Double
+        | SqlType::Decimal(_, _) => 1,
+        SqlType::Varchar(_) | SqlType::Text => 2,
+        SqlType::Date | SqlType::DateTime => 3,
+    }
+}
+
+/// 整数位数和小数位数
+fn digits(sql_type: &SqlType) -> (usize, usize) {
+    match sql_type {
+        SqlType::Integer => (10, 0),
+        SqlType::BigInt => (19, 0),
+        SqlType::Decimal(p, s) => (p.saturating_sub(*s), *s),
+        _ => (0, 0),
+    }
+}
+
+/// 合并同类的两个类型，得到能容纳两者所有值的类型
+fn widen(a: &SqlType, b: &SqlType) -> SqlType {
+    match (a, b) {
+        _ if a == b => a.clone(),
+        (SqlType::Text, _) | (_, SqlType::Text) => SqlType::Text,
+        (SqlType::Varchar(x), SqlType::Varchar(y)) => SqlType::Varchar(*x.max(y)),
+        (SqlType::Date | SqlType::DateTime, SqlType::Date | SqlType::DateTime) => SqlType::DateTime,
+        (SqlType::Float, SqlType::Float) => SqlType::Float,
+        (SqlType::Float | SqlType::Double, _) | (_, SqlType::Float | SqlType::Double) => {
+            SqlType::Double
+        }
+        _ => {
+            let ((int_a, scale_a), (int_b, scale_b)) = (digits(a), digits(b));
+            let (int_digits, scale) = (int_a.max(int_b), scale_a.max(scale_b));
+            match (int_digits, scale) {
+                (0..=10, 0) => SqlType::Integer,
+                (11..=19, 0) => SqlType::BigInt,
+                _ if int_digits + scale <= 38 => SqlType::Decimal(int_digits + scale, scale),
+                _ => SqlType::Double,
+            }
+        }
+    }
+}
+
+/// 值的文本形式的最大长度
+fn text_width(sql_type: &SqlType) -> usize {
+    match sql_type {
+        SqlType::Boolean => 5,
+        SqlType::Integer => 11,
+        SqlType::BigInt => 20,
+        SqlType::Float | SqlType::Double => 24,
+        SqlType::Decimal(p, _) => p + 2,
+        SqlType::Date => 10,
+        SqlType::DateTime => 19,
+        SqlType::Varchar(len) => *len,
+        SqlType::Text => usize::MAX,
+    }
+}
+
+/// `from` 类型的值转换为 `to` 类型是否不会丢失数据
+fn is_lossless(from: &SqlType, to: &SqlType) -> bool {
+    match (from, to) {
+        _ if from == to => true,
+        (_, SqlType::Text) => true,
+        (_, SqlType::Varchar(len)) => text_width(from) <= *len,
+        (SqlType::Boolean, _) => type_family(to) == 1,
+        (SqlType::Integer, SqlType::BigInt | SqlType::Double) => true,
+        (SqlType::Integer, SqlType::Decimal(p, s)) => p.saturating_sub(*s) >= 10,
+        (SqlType::BigInt, SqlType::Decimal(p, s)) => p.saturating_sub(*s) >= 19,
+        (SqlType::Decimal(p1, s1), SqlType::Decimal(p2, s2)) => {
+            s2 >= s1 && p2.saturating_sub(*s2) >= p1.saturating_sub(*s1)
+        }
+        (SqlType::Decimal(_, _) | SqlType::Float, SqlType::Double) => true,
+        (SqlType::Date, SqlType::DateTime) => true,
+        _ => false,
+    }
+}
+
+/// 解析 CREATE TABLE 脚本中的第一张表，用于与新数据对比
+///
+/// 支持各方言的引用标识符、列级和表级 PRIMARY KEY、NOT NULL 以及 ClickHouse 的 `Nullable(...)`
+pub fn parse_create_table(sql: &str) -> Result<TableSchema> {
+    let sql = strip_comments(sql);
+    let upper = sql.to_ascii_uppercase();
+    let start = upper
+        .find("CREATE ")
+        .and_then(|i| upper[i..].find(" TABLE ").map(|j| i + j + " TABLE ".len()))
+        .ok_or_else(|| ExcelCliError::ExportError("脚本中没有 CREATE TABLE 语句".to_string()))?;
+    let open = sql[start..]
+        .find('(')
+        .map(|i| start + i)
+        .ok_or_else(|| ExcelCliError::ExportError("CREATE TABLE 语句缺少列定义".to_string()))?;
+
+    let mut name = sql[start..open].trim();
+    if name.to_ascii_uppercase().starts_with("IF NOT EXISTS") {
+        name = name["IF NOT EXISTS".len()..].trim();
+    }
+    let name = split_top_level(name, '.')
+        .iter()
+        .map(|part| unquote(part))
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let body = matching_paren(&sql[open..])
+        .ok_or_else(|| ExcelCliError::ExportError("CREATE TABLE 语句的括号不匹配".to_string()))?;
+
+    let mut columns = Vec::new();
+    let mut primary_key = Vec::new();
+    for item in split_top_level(body, ',') {
+        let words = split_top_level(&item, ' ');
+        let Some(first) = words.first() else {
+            continue;
+        };
+        let keyword = first.to_uppercase();
+        if matches!(
+            keyword.as_str(),
+            "CONSTRAINT" | "PRIMARY" | "UNIQUE" | "KEY" | "INDEX" | "FOREIGN" | "CHECK"
+        ) {
+            // 表级主键约束
+            let upper_item = item.to_ascii_uppercase();
+            if let Some(pos) = upper_item.find("PRIMARY KEY") {
+                let rest = item[pos + "PRIMARY KEY".len()..].trim_start();
+                if let Some(columns) = matching_paren(rest) {
+                    primary_key.extend(split_top_level(columns, ',').iter().map(|c| unquote(c)));
+                }
+            }
+            continue;
+        }
+
+        let column_name = unquote(first);
+        let type_words: Vec<&str> = words[1..]
+            .iter()
+            .take_while(|w| !is_constraint_keyword(w))
+            .map(String::as_str)
+            .collect();
+        let type_name = type_words.join(" ");
+        let constraints = words[1 + type_words.len()..].join(" ").to_uppercase();
+
+        let (type_name, clickhouse_nullable) = match type_name.strip_suffix(')') {
+            Some(inner) if type_name.to_ascii_uppercase().starts_with("NULLABLE(") => {
+                (inner["NULLABLE(".len()..].to_string(), true)
+            }
+            _ => (type_name, false),
+        };
+        let sql_type = parse_sql_type(&type_name).ok_or_else(|| {
+            ExcelCliError::ExportError(format!(
+                "无法识别列 '{}' 的类型: {}",
+                column_name, type_name
+            ))
+        })?;
+
+        let inline_primary_key = constraints.contains("PRIMARY KEY");
+        if inline_primary_key {
+            primary_key.push(column_name.clone());
+        }
+        // ClickHouse 的列默认不可为空
+        let nullable = if upper.contains("ENGINE") && upper.contains("MERGETREE") {
+            clickhouse_nullable
+        } else {
+            !constraints.contains("NOT NULL") && !inline_primary_key
+        };
+
+        columns.push(ColumnSchema {
+            name: column_name.clone(),
+            source: column_name,
+            sql_type,
+            nullable,
+            unique_candidate: false,
+            comment: None,
+            outliers: Vec::new(),
+        });
+    }
+
+    if columns.is_empty() {
+        return Err(ExcelCliError::ExportError(
+            "CREATE TABLE 语句中没有列".to_string(),
+        ));
+    }
+    for column in columns.iter_mut() {
+        if primary_key.iter().any(|k| k == &column.name) {
+            column.nullable = false;
+        }
+    }
+
+    Ok(TableSchema {
+        name,
+        columns,
+        primary_key,
+        indexes: Vec::new(),
+        comment: None,
+    })
+}
+
+/// 把各方言的类型名映射回 `SqlType`
+fn parse_sql_type(type_name: &str) -> Option<SqlType> {
+    let type_name = type_name.trim().to_uppercase();
+    let (base, args) = match type_name.find('(') {
+        Some(i) => (
+            type_name[..i].trim(),
+            type_name[i + 1..]
+                .trim_end_matches(')')
+                .split(',')
+                .map(|a| a.trim())
+                .collect::<Vec<_>>(),
+        ),
+        None => (type_name.as_str(), Vec::new()),
+    };
+    let arg = |i: usize| args.get(i).and_then(|a| a.parse::<usize>().ok());
+
+    let sql_type = match base {
+        "INT" | "INTEGER" | "INT4" | "INT32" | "MEDIUMINT" | "SMALLINT" | "INT16" => {
+            SqlType::Integer
+        }
+        "BIGINT" | "INT8" | "INT64" => SqlType::BigInt,
+        "TINYINT" if arg(0) == Some(1) => SqlType::Boolean,
+        "TINYINT" => SqlType::Integer,
+        "BOOLEAN" | "BOOL" | "BIT" => SqlType::Boolean,
+        "FLOAT" | "REAL" | "FLOAT4" | "FLOAT32" | "BINARY_FLOAT" => SqlType::Float,
+        "DOUBLE" | "DOUBLE PRECISION" | "FLOAT8" | "FLOAT64" | "BINARY_DOUBLE" => SqlType::Double,
+        // Oracle 用 NUMBER(p) 表示整数和布尔
+        "NUMBER" if args.len() == 1 => match arg(0)? {
+            1 => SqlType::Boolean,
+            p if p <= 10 => SqlType::Integer,
+            p if p <= 19 => SqlType::BigInt,
+            p => SqlType::Decimal(p, 0),
+        },
+        "DECIMAL" | "NUMERIC" | "NUMBER" | "DEC" => match (arg(0), arg(1)) {
+            (Some(p), Some(s)) => SqlType::Decimal(p, s),
+            (Some(p), None) => SqlType::Decimal(p, 0),
+            _ => SqlType::Decimal(38, 6),
+        },
+        "VARCHAR" | "VARCHAR2" | "NVARCHAR" | "NVARCHAR2" | "CHAR" | "NCHAR"
+        | "CHARACTER VARYING" | "CHARACTER"
+            if arg(0).is_some() =>
+        {
+            SqlType::Varchar(arg(0)?)
+        }
+        "VARCHAR" | "NVARCHAR" | "CHARACTER VARYING" | "TEXT" | "CLOB" | "NCLOB" | "STRING"
+        | "MEDIUMTEXT" | "LONGTEXT" | "TINYTEXT" => SqlType::Text,
+        "DATE" => SqlType::Date,
+        "DATETIME" | "DATETIME2" | "TIMESTAMP" | "TIMESTAMP_NTZ" | "TIMESTAMP_LTZ"
+        | "TIMESTAMP_TZ" | "SMALLDATETIME" | "DATETIME64" => SqlType::DateTime,
+        _ if base.starts_with("TIMESTAMP") => SqlType::DateTime,
+        _ => return None,
+    };
+    Some(sql_type)
+}
+
+/// 列定义中类型之后的约束关键字
+fn is_constraint_keyword(word: &str) -> bool {
+    matches!(
+        word.to_uppercase().as_str(),
+        "NOT"
+            | "NULL"
+            | "PRIMARY"
+            | "DEFAULT"
+            | "COMMENT"
+            | "UNIQUE"
+            | "REFERENCES"
+            | "CHECK"
+            | "CONSTRAINT"
+            | "AUTO_INCREMENT"
+            | "AUTOINCREMENT"
+            | "IDENTITY"
+            | "GENERATED"
+            | "OPTIONS"
+            | "COLLATE"
+            | "CODEC"
+    )
+}
+
+/// 去掉 `--` 和 `/* */` 注释（忽略字符串中的内容）
+fn strip_comments(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            in_string = c != '\'';
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('\'', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('-', Some('-')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 返回以 `(` 开头的文本中与之匹配的括号内的内容
+fn matching_paren(s: &str) -> Option<&str> {
+    if !s.starts_with('(') {
+        return None;
+    }
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&s[1..i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 按括号和引号之外的分隔符拆分，空白分隔符匹配任意空白
+fn split_top_level(s: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for c in s.chars() {
+        let is_separator = if separator == ' ' {
+            c.is_whitespace()
+        } else {
+            c == separator
+        };
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, _) if depth == 0 && is_separator => {
+                if !current.trim().is_empty() {
+                    parts.push(current.trim().to_string());
+                }
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+/// 去掉标识符两侧的引用字符
+fn unquote(identifier: &str) -> String {
+    let identifier = identifier.trim();
+    let mut chars = identifier.chars();
+    match (chars.next(), chars.next_back()) {
+        (Some('`'), Some('`')) => identifier[1..identifier.len() - 1].replace("``", "`"),
+        (Some('"'), Some('"')) => identifier[1..identifier.len() - 1].replace("\"\"", "\""),
+        (Some('['), Some(']')) => identifier[1..identifier.len() - 1].replace("]]", "]"),
+        _ => identifier.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_diff() {
+        let old = TableSchema {
+            name: "orders".to_string(),
+            columns: vec![
+                ColumnSchema::new("id", SqlType::Integer, false),
+                ColumnSchema::new("amount", SqlType::Integer, false),
+                ColumnSchema::new("code", SqlType::Varchar(80), false),
+                ColumnSchema::new("note", SqlType::Varchar(60), true),
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: Vec::new(),
+            comment: None,
+        };
+        let new = TableSchema {
+            name: "orders".to_string(),
+            columns: vec![
+                ColumnSchema::new("id", SqlType::Integer, false),
+                ColumnSchema::new("amount", SqlType::Decimal(8, 2), true),
+                ColumnSchema::new("code", SqlType::Integer, false),
+                ColumnSchema::new("shipped", SqlType::Date, false),
+            ],
+            ..old.clone()
+        };
+
+        let diff = SchemaDiff::compare(&old, &new, SqlDialect::PostgreSQL);
+        assert_eq!(diff.lossy_changes().len(), 1);
+        assert_eq!(
+            diff.alter_sqls(false),
+            vec![
+                "ALTER TABLE \"orders\" ADD COLUMN \"shipped\" DATE;",
+                "ALTER TABLE \"orders\" ALTER COLUMN \"amount\" TYPE NUMERIC(12, 2);",
+                "ALTER TABLE \"orders\" ALTER COLUMN \"amount\" DROP NOT NULL;",
+                "-- ⚠️ 类型收窄可能丢失数据: code VARCHAR(80) -> INTEGER",
+                "-- ALTER TABLE \"orders\" ALTER COLUMN \"code\" TYPE INTEGER USING \"code\"::INTEGER;",
+                "-- ⚠️ 删除列会丢失数据: note",
+                "-- ALTER TABLE \"orders\" DROP COLUMN \"note\";",
+            ]
+        );
+        assert_eq!(diff.dropped_columns(), vec!["note"]);
+        assert!(diff
+            .alter_sqls(true)
+            .contains(&"ALTER TABLE \"orders\" DROP COLUMN \"note\";".to_string()));
+
+        let diff = SchemaDiff::compare(&old, &new, SqlDialect::MySQL);
+        let sqls = diff.alter_sqls(true);
+        assert!(sqls
+            .contains(&"ALTER TABLE `orders` MODIFY COLUMN `amount` DECIMAL(12, 2);".to_string()));
+        assert!(
+            sqls.contains(&"ALTER TABLE `orders` MODIFY COLUMN `code` INT NOT NULL;".to_string())
+        );
+
+        // 数据变短不收窄已有的列
+        let shorter = TableSchema {
+            columns: vec![ColumnSchema::new("note", SqlType::Varchar(55), true)],
+            ..old.clone()
+        };
+        let diff = SchemaDiff::compare(&old, &shorter, SqlDialect::MySQL);
+        assert!(diff
+            .changes
+            .iter()
+            .all(|c| matches!(c, SchemaChange::DropColumn(_))));
+    }
+
+    #[test]
+    fn test_parse_create_table() {
+        let sql = "-- 生成的表\nCREATE TABLE IF NOT EXISTS `shop`.`orders` (\n    `id` INT PRIMARY KEY,\n    `amount` DECIMAL(8, 2) NOT NULL COMMENT '金额, 元',\n    `name` VARCHAR(80),\n    `created` DATETIME\n);";
+        let schema = parse_create_table(sql).unwrap();
+        assert_eq!(schema.name, "shop.orders");
+        assert_eq!(schema.primary_key, vec!["id"]);
+        assert_eq!(schema.columns.len(), 4);
+        assert_eq!(schema.columns[1].sql_type, SqlType::Decimal(8, 2));
+        assert!(!schema.columns[1].nullable);
+        assert!(schema.columns[2].nullable);
+        assert_eq!(schema.columns[3].sql_type, SqlType::DateTime);
+
+        let sql = "CREATE TABLE [dbo].[t] (\n    [Order Id] BIGINT NOT NULL,\n    [Price] FLOAT,\n    CONSTRAINT pk_t PRIMARY KEY ([Order Id])\n);";
+        let schema = parse_create_table(sql).unwrap();
+        assert_eq!(schema.name, "dbo.t");
+        assert_eq!(schema.primary_key, vec!["Order Id"]);
+        assert_eq!(schema.columns[0].sql_type, SqlType::BigInt);
+
+        let sql = "CREATE TABLE t (\n    `id` Int32,\n    `name` Nullable(String)\n) ENGINE = MergeTree()\nORDER BY (`id`);";
+        let schema = parse_create_table(sql).unwrap();
+        assert!(!schema.columns[0].nullable);
+        assert!(schema.columns[1].nullable);
+        assert_eq!(schema.columns[1].sql_type, SqlType::Text);
+    }
+}
@@ -0,0 +1,521 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::error::{ExcelCliError, Result};
+use crate::identifier::split_words;
+use crate::schema::{ColumnSchema, SqlType, TableSchema};
+
+/// 表结构的非 SQL 输出目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaTarget {
+    /// JSON Schema（draft 2020-12），属性名与 JSON 导出的键一致
+    JsonSchema,
+    /// Avro 记录 schema
+    Avro,
+    /// Protocol Buffers（proto3）消息
+    Protobuf,
+    /// TypeScript 接口
+    TypeScript,
+    /// 带 serde 注解的 Rust 结构体
+    RustStruct,
+    /// Pydantic 模型
+    Pydantic,
+}
+
+impl SchemaTarget {
+    /// 从字符串解析输出目标
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jsonschema" | "json-schema" | "json_schema" => Ok(SchemaTarget::JsonSchema),
+            "avro" => Ok(SchemaTarget::Avro),
+            "protobuf" | "proto" => Ok(SchemaTarget::Protobuf),
+            "typescript" | "ts" => Ok(SchemaTarget::TypeScript),
+            "rust-struct" | "rust_struct" | "rust" => Ok(SchemaTarget::RustStruct),
+            "pydantic" | "python" => Ok(SchemaTarget::Pydantic),
+            _ => Err(ExcelCliError::UnsupportedFormat(format!(
+                "不支持的 Schema 输出目标: {}",
+                s
+            ))),
+        }
+    }
+
+    /// 把推断的表结构转换为目标格式
+    pub fn render(&self, schema: &TableSchema) -> Result<String> {
+        match self {
+            SchemaTarget::JsonSchema => json_schema(schema),
+            SchemaTarget::Avro => avro(schema),
+            SchemaTarget::Protobuf => Ok(protobuf(schema)),
+            SchemaTarget::TypeScript => Ok(typescript(schema)),
+            SchemaTarget::RustStruct => Ok(rust_struct(schema)),
+            SchemaTarget::Pydantic => Ok(pydantic(schema)),
+        }
+    }
+}
+
+/// Rust 关键字，用作字段名时追加下划线
+const RUST_KEYWORDS: &str = "\
+    as async await break const continue crate dyn else enum extern false fn for if impl in \
+    let loop match mod move mut pub ref return self static struct super trait true type \
+    unsafe use where while abstract become box do final macro override priv typeof unsized \
+    virtual yield try";
+
+/// Python 关键字和 Pydantic 模型的内置属性，用作字段名时追加下划线
+const PYTHON_KEYWORDS: &str = "\
+    False None True and as assert async await break class continue def del elif else \
+    except finally for from global if import in is lambda nonlocal not or pass raise \
+    return try while with yield model_config model_fields";
+
+/// Protobuf 的保留字
+const PROTOBUF_KEYWORDS: &str = "\
+    syntax import weak public package option message enum service rpc returns stream \
+    reserved extensions to max oneof map optional repeated required";
+
+/// 表名转换为类型名（PascalCase）
+fn type_name(table: &str) -> String {
+    let short = table.rsplit('.').next().unwrap_or(table);
+    let name: String = split_words(&deunicode::deunicode(short), true)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_lowercase()
+                }
+                None => String::new(),
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Record".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("T{}", name),
+        Some(_) => name,
+    }
+}
+
+/// 列名转换为 snake_case 字段名：音译非 ASCII 字符，规避关键字并保证不重名
+fn field_names(columns: &[ColumnSchema], keywords: &str) -> Vec<String> {
+    let mut used = HashSet::new();
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let mut name = split_words(&deunicode::deunicode(&column.name), true)
+                .join("_")
+                .to_lowercase();
+            if name.is_empty() {
+                name = format!("field_{}", i + 1);
+            } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+                name.insert_str(0, "field_");
+            }
+            if keywords.split_whitespace().any(|k| k == name) {
+                name.push('_');
+            }
+            let mut unique = name.clone();
+            let mut n = 2;
+            while !used.insert(unique.clone()) {
+                unique = format!("{}_{}", name, n);
+                n += 1;
+            }
+            unique
+        })
+        .collect()
+}
+
+/// 是否可以直接作为 JavaScript 属性名
+fn is_js_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// 列在 JSON 导出中的键，即 Excel 中的原始列名（旧版保存的 schema 没有原始列名时用列名）
+fn json_key(column: &ColumnSchema) -> &str {
+    if column.source.is_empty() {
+        &column.name
+    } else {
+        &column.source
+    }
+}
+
+/// 转义为带双引号的字符串字面量（JSON 转义规则同样适用于 Rust、Python 和 TypeScript）
+fn quoted(s: &str) -> String {
+    Value::String(s.to_string()).to_string()
+}
+
+fn json_schema(schema: &TableSchema) -> Result<String> {
+    let mut properties = Map::new();
+    for column in &schema.columns {
+        let mut property = match column.sql_type {
+            SqlType::Integer | SqlType::BigInt => json!({ "type": "integer" }),
+            SqlType::Float | SqlType::Double | SqlType::Decimal(_, _) => {
+                json!({ "type": "number" })
+            }
+            SqlType::Boolean => json!({ "type": "boolean" }),
+            SqlType::Varchar(_) | SqlType::Text => json!({ "type": "string" }),
+            SqlType::Date => json!({ "type": "string", "format": "date" }),
+            SqlType::DateTime => json!({ "type": "string", "format": "date-time" }),
+        };
+        if column.nullable {
+            let base = property["type"].clone();
+            property["type"] = json!([base, "null"]);
+        }
+        if let Some(comment) = &column.comment {
+            property["description"] = json!(comment);
+        }
+        properties.insert(json_key(column).to_string(), property);
+    }
+
+    let required: Vec<&str> = schema
+        .columns
+        .iter()
+        .filter(|c| !c.nullable)
+        .map(json_key)
+        .collect();
+    // JSON 导出的结果是行对象的数组
+    let mut root = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": schema.name,
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        },
+    });
+    if let Some(comment) = &schema.comment {
+        root["description"] = json!(comment);
+    }
+    Ok(serde_json::to_string_pretty(&root)?)
+}
+
+fn avro(schema: &TableSchema) -> Result<String> {
+    let names = field_names(&schema.columns, "");
+    let fields: Vec<Value> = schema
+        .columns
+        .iter()
+        .zip(&names)
+        .map(|(column, name)| {
+            let avro_type = match column.sql_type {
+                SqlType::Integer => json!("int"),
+                SqlType::BigInt => json!("long"),
+                SqlType::Float => json!("float"),
+                SqlType::Double => json!("double"),
+                SqlType::Decimal(precision, scale) => json!({
+                    "type": "bytes",
+                    "logicalType": "decimal",
+                    "precision": precision,
+                    "scale": scale,
+                }),
+                SqlType::Boolean => json!("boolean"),
+                SqlType::Varchar(_) | SqlType::Text => json!("string"),
+                SqlType::Date => json!({ "type": "int", "logicalType": "date" }),
+                // 表格中的日期时间不带时区
+                SqlType::DateTime => {
+                    json!({ "type": "long", "logicalType": "local-timestamp-millis" })
+                }
+            };
+            let mut field = if column.nullable {
+                json!({ "name": name, "type": ["null", avro_type], "default": null })
+            } else {
+                json!({ "name": name, "type": avro_type })
+            };
+            if let Some(comment) = &column.comment {
+                field["doc"] = json!(comment);
+            }
+            field
+        })
+        .collect();
+
+    let mut record = json!({
+        "type": "record",
+        "name": type_name(&schema.name),
+        "fields": fields,
+    });
+    if let Some(comment) = &schema.comment {
+        record["doc"] = json!(comment);
+    }
+    Ok(serde_json::to_string_pretty(&record)?)
+}
+
+fn protobuf(schema: &TableSchema) -> String {
+    let names = field_names(&schema.columns, PROTOBUF_KEYWORDS);
+    let uses_timestamp = schema
+        .columns
+        .iter()
+        .any(|c| c.sql_type == SqlType::DateTime);
+
+    let mut out = String::from("syntax = \"proto3\";\n\n");
+    if uses_timestamp {
+        out.push_str("import \"google/protobuf/timestamp.proto\";\n\n");
+    }
+    if let Some(comment) = &schema.comment {
+        out.push_str(&format!("// {}\n", comment));
+    }
+    out.push_str(&format!("message {} {{\n", type_name(&schema.name)));
+    for (i, (column, name)) in schema.columns.iter().zip(&names).enumerate() {
+        let (proto_type, note) = match column.sql_type {
+            SqlType::Integer => ("int32", None),
+            SqlType::BigInt => ("int64", None),
+            SqlType::Float => ("float", None),
+            SqlType::Double => ("double", None),
+            // 定点小数用字符串传输，避免精度损失
+            SqlType::Decimal(_, _) => ("string", Some("decimal")),
+            SqlType::Boolean => ("bool", None),
+            SqlType::Varchar(_) | SqlType::Text => ("string", None),
+            SqlType::Date => ("string", Some("YYYY-MM-DD")),
+            SqlType::DateTime => ("google.protobuf.Timestamp", None),
+        };
+        if let Some(comment) = &column.comment {
+            out.push_str(&format!("  // {}\n", comment));
+        }
+        let json_name = if name != json_key(column) {
+            format!(" [json_name = {}]", quoted(json_key(column)))
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "  {}{} {} = {}{};{}\n",
+            if column.nullable { "optional " } else { "" },
+            proto_type,
+            name,
+            i + 1,
+            json_name,
+            note.map(|n| format!(" // {}", n)).unwrap_or_default()
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn typescript(schema: &TableSchema) -> String {
+    let mut out = String::new();
+    if let Some(comment) = &schema.comment {
+        out.push_str(&format!("/** {} */\n", comment));
+    }
+    out.push_str(&format!(
+        "export interface {} {{\n",
+        type_name(&schema.name)
+    ));
+    for column in &schema.columns {
+        let ts_type = match column.sql_type {
+            SqlType::Integer
+            | SqlType::BigInt
+            | SqlType::Float
+            | SqlType::Double
+            | SqlType::Decimal(_, _) => "number",
+            SqlType::Boolean => "boolean",
+            // JSON 中的日期为 ISO 8601 字符串
+            SqlType::Varchar(_) | SqlType::Text | SqlType::Date | SqlType::DateTime => "string",
+        };
+        if let Some(comment) = &column.comment {
+            out.push_str(&format!("  /** {} */\n", comment));
+        }
+        let key = json_key(column);
+        let property = if is_js_identifier(key) {
+            key.to_string()
+        } else {
+            quoted(key)
+        };
+        out.push_str(&format!(
+            "  {}: {}{};\n",
+            property,
+            ts_type,
+            if column.nullable { " | null" } else { "" }
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn rust_struct(schema: &TableSchema) -> String {
+    let names = field_names(&schema.columns, RUST_KEYWORDS);
+    let mut out = String::from("use serde::{Deserialize, Serialize};\n\n");
+    if let Some(comment) = &schema.comment {
+        out.push_str(&format!("/// {}\n", comment));
+    }
+    out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+    out.push_str(&format!("pub struct {} {{\n", type_name(&schema.name)));
+    for (column, name) in schema.columns.iter().zip(&names) {
+        let rust_type = match column.sql_type {
+            SqlType::Integer => "i32",
+            SqlType::BigInt => "i64",
+            SqlType::Float => "f32",
+            SqlType::Double | SqlType::Decimal(_, _) => "f64",
+            SqlType::Boolean => "bool",
+            SqlType::Varchar(_) | SqlType::Text => "String",
+            SqlType::Date => "chrono::NaiveDate",
+            SqlType::DateTime => "chrono::NaiveDateTime",
+        };
+        if let Some(comment) = &column.comment {
+            out.push_str(&format!("    /// {}\n", comment));
+        }
+        if name != json_key(column) {
+            out.push_str(&format!(
+                "    #[serde(rename = {})]\n",
+                quoted(json_key(column))
+            ));
+        }
+        let rust_type = if column.nullable {
+            format!("Option<{}>", rust_type)
+        } else {
+            rust_type.to_string()
+        };
+        out.push_str(&format!("    pub {}: {},\n", name, rust_type));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn pydantic(schema: &TableSchema) -> String {
+    let names = field_names(&schema.columns, PYTHON_KEYWORDS);
+    let has = |f: fn(&SqlType) -> bool| schema.columns.iter().any(|c| f(&c.sql_type));
+
+    let mut imports = Vec::new();
+    match (
+        has(|t| *t == SqlType::Date),
+        has(|t| *t == SqlType::DateTime),
+    ) {
+        (true, true) => imports.push("from datetime import date, datetime"),
+        (true, false) => imports.push("from datetime import date"),
+        (false, true) => imports.push("from datetime import datetime"),
+        (false, false) => {}
+    }
+    if has(|t| matches!(t, SqlType::Decimal(_, _))) {
+        imports.push("from decimal import Decimal");
+    }
+    if schema.columns.iter().any(|c| c.nullable) {
+        imports.push("from typing import Optional");
+    }
+    imports.push("");
+    imports.push("from pydantic import BaseModel, ConfigDict, Field");
+
+    let mut out = imports.join("\n");
+    out.push_str(&format!(
+        "\n\n\nclass {}(BaseModel):\n",
+        type_name(&schema.name)
+    ));
+    if let Some(comment) = &schema.comment {
+        out.push_str(&format!("    {}\n\n", quoted(comment)));
+    }
+    out.push_str("    model_config = ConfigDict(populate_by_name=True)\n\n");
+    for (column, name) in schema.columns.iter().zip(&names) {
+        let py_type = match column.sql_type {
+            SqlType::Integer | SqlType::BigInt => "int",
+            SqlType::Float | SqlType::Double => "float",
+            SqlType::Decimal(_, _) => "Decimal",
+            SqlType::Boolean => "bool",
+            SqlType::Varchar(_) | SqlType::Text => "str",
+            SqlType::Date => "date",
+            SqlType::DateTime => "datetime",
+        };
+        let mut field_args = Vec::new();
+        if column.nullable {
+            field_args.push("default=None".to_string());
+        }
+        if name != json_key(column) {
+            field_args.push(format!("alias={}", quoted(json_key(column))));
+        }
+        if let Some(comment) = &column.comment {
+            field_args.push(format!("description={}", quoted(comment)));
+        }
+
+        let py_type = if column.nullable {
+            format!("Optional[{}]", py_type)
+        } else {
+            py_type.to_string()
+        };
+        let default = match field_args.as_slice() {
+            [] => String::new(),
+            [only] if only == "default=None" => " = None".to_string(),
+            _ => format!(" = Field({})", field_args.join(", ")),
+        };
+        out.push_str(&format!("    {}: {}{}\n", name, py_type, default));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> TableSchema {
+        let column = ColumnSchema::new;
+        TableSchema {
+            name: "sales.order_items".to_string(),
+            columns: vec![
+                column("ID", SqlType::Integer, false),
+                column("Order Date (UTC)", SqlType::DateTime, true),
+                column("type", SqlType::Varchar(60), false),
+                column("金额", SqlType::Decimal(10, 2), true),
+                // 规范化后的列名与 JSON 导出的键不同
+                ColumnSchema {
+                    source: "Customer Name".to_string(),
+                    ..column("customer_name", SqlType::Text, false)
+                },
+            ],
+            primary_key: vec!["ID".to_string()],
+            indexes: Vec::new(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_json_schema_and_avro() {
+        let schema = sample();
+        let json: Value =
+            serde_json::from_str(&SchemaTarget::JsonSchema.render(&schema).unwrap()).unwrap();
+        assert_eq!(json["type"], "array");
+        assert!(json.get("description").is_none());
+        let items = &json["items"];
+        assert_eq!(items["properties"]["ID"]["type"], "integer");
+        assert_eq!(
+            items["properties"]["Order Date (UTC)"]["type"],
+            json!(["string", "null"])
+        );
+        assert_eq!(items["required"], json!(["ID", "type", "Customer Name"]));
+        assert!(items["properties"].get("customer_name").is_none());
+
+        let avro: Value =
+            serde_json::from_str(&SchemaTarget::Avro.render(&schema).unwrap()).unwrap();
+        assert_eq!(avro["name"], "OrderItems");
+        assert_eq!(avro["fields"][1]["name"], "order_date_utc");
+        assert_eq!(avro["fields"][1]["type"][0], "null");
+        assert_eq!(avro["fields"][3]["name"], "jin_e");
+        assert_eq!(avro["fields"][3]["type"][1]["logicalType"], "decimal");
+    }
+
+    #[test]
+    fn test_code_targets() {
+        let schema = sample();
+
+        let proto = SchemaTarget::Protobuf.render(&schema).unwrap();
+        assert!(proto.contains("import \"google/protobuf/timestamp.proto\";"));
+        assert!(proto.contains("message OrderItems {\n  int32 id = 1 [json_name = \"ID\"];\n"));
+        assert!(proto.contains(
+            "  optional google.protobuf.Timestamp order_date_utc = 2 [json_name = \"Order Date (UTC)\"];\n"
+        ));
+
+        let ts = SchemaTarget::TypeScript.render(&schema).unwrap();
+        assert!(ts.contains("  \"Order Date (UTC)\": string | null;\n"));
+        assert!(ts.contains("  type: string;\n"));
+        assert!(ts.contains("  \"Customer Name\": string;\n"));
+
+        let rust = SchemaTarget::RustStruct.render(&schema).unwrap();
+        assert!(rust.contains("    #[serde(rename = \"type\")]\n    pub type_: String,\n"));
+        assert!(rust.contains("    pub order_date_utc: Option<chrono::NaiveDateTime>,\n"));
+        assert!(rust.contains(
+            "    #[serde(rename = \"Customer Name\")]\n    pub customer_name: String,\n"
+        ));
+
+        let py = SchemaTarget::Pydantic.render(&schema).unwrap();
+        assert!(py.starts_with("from datetime import datetime\nfrom decimal import Decimal\n"));
+        assert!(py.contains("class OrderItems(BaseModel):\n"));
+        assert!(py.contains("    id: int = Field(alias=\"ID\")\n"));
+        assert!(py.contains("    jin_e: Optional[Decimal] = Field(default=None, alias=\"金额\")\n"));
+        assert!(py.contains("    customer_name: str = Field(alias=\"Customer Name\")\n"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{ExcelCliError, Result};
+use crate::models::ExcelData;
+
+/// 拆分后的一份数据
+#[derive(Debug, Clone)]
+pub struct DataChunk {
+    /// 序号，从 1 开始，在所有拆分结果中连续编号
+    pub index: usize,
+    /// 按列拆分时该份数据的列值
+    pub group: Option<String>,
+    /// 数据
+    pub data: ExcelData,
+}
+
+/// 数据拆分器：按列值分组、按行数分块，或先分组再分块
+pub struct DataSplitter {
+    column: Option<String>,
+    max_rows: Option<usize>,
+}
+
+impl Default for DataSplitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataSplitter {
+    /// 创建新的拆分器（默认不拆分）
+    pub fn new() -> Self {
+        Self {
+            column: None,
+            max_rows: None,
+        }
+    }
+
+    /// 设置按哪一列的值分组
+    pub fn with_column(mut self, column: String) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// 设置每份数据的最大行数
+    pub fn with_max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows.max(1));
+        self
+    }
+
+    /// 拆分数据，分组按列值第一次出现的顺序排列；没有数据行时返回空列表
+    pub fn split(&self, data: &ExcelData) -> Result<Vec<DataChunk>> {
+        let mut groups: Vec<(Option<String>, ExcelData)> = Vec::new();
+        match &self.column {
+            Some(column) => {
+                if !data.headers.contains(column) {
+                    return Err(ExcelCliError::ExportError(format!(
+                        "拆分列 '{}' 不存在",
+                        column
+                    )));
+                }
+                let mut positions: HashMap<String, usize> = HashMap::new();
+                for row in &data.rows {
+                    let value = row
+                        .data
+                        .get(column)
+                        .map(|v| v.to_string())
+                        .unwrap_or_default();
+                    let position = *positions.entry(value.clone()).or_insert_with(|| {
+                        groups.push((
+                            Some(value),
+                            ExcelData::new(data.sheet_name.clone(), data.headers.clone()),
+                        ));
+                        groups.len() - 1
+                    });
+                    groups[position].1.add_row(row.clone());
+                }
+            }
+            None if data.rows.is_empty() => {}
+            None => groups.push((None, data.clone())),
+        }
+
+        let mut chunks = Vec::new();
+        for (group, data) in groups {
+            let max_rows = self.max_rows.unwrap_or(data.row_count().max(1));
+            for rows in data.rows.chunks(max_rows) {
+                chunks.push(DataChunk {
+                    index: chunks.len() + 1,
+                    group: group.clone(),
+                    data: ExcelData {
+                        sheet_name: data.sheet_name.clone(),
+                        headers: data.headers.clone(),
+                        rows: rows.to_vec(),
+                    },
+                });
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// 按文件名模板生成每份数据的输出路径
+    ///
+    /// 模板中的 `{n}` 替换为序号，`{拆分列名}` 替换为列值（文件名中的非法字符替换为 `_`）；
+    /// 生成的路径重复时报错，避免后面的文件覆盖前面的
+    pub fn output_paths(&self, template: &str, chunks: &[DataChunk]) -> Result<Vec<String>> {
+        let mut paths = Vec::with_capacity(chunks.len());
+        let mut seen = HashSet::new();
+        for chunk in chunks {
+            let path = self.render(template, chunk)?;
+            if !seen.insert(path.clone()) {
+                return Err(ExcelCliError::ExportError(format!(
+                    "输出路径 '{}' 重复，请在模板中加入 {{n}}{}",
+                    path,
+                    self.column
+                        .as_ref()
+                        .map(|c| format!(" 或 {{{}}}", c))
+                        .unwrap_or_default()
+                )));
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// 替换模板中的占位符
+    fn render(&self, template: &str, chunk: &DataChunk) -> Result<String> {
+        let mut path = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 1..start + len];
+            path.push_str(&rest[..start]);
+            if name == "n" {
+                path.push_str(&chunk.index.to_string());
+            } else if self.column.as_deref() == Some(name) {
+                path.push_str(&file_name_safe(chunk.group.as_deref().unwrap_or_default()));
+            } else {
+                return Err(ExcelCliError::ExportError(format!(
+                    "输出路径模板中的占位符 {{{}}} 不是 {{n}} 或拆分列",
+                    name
+                )));
+            }
+            rest = &rest[start + len + 1..];
+        }
+        path.push_str(rest);
+        Ok(path)
+    }
+}
+
+/// 把列值转换为可用作文件名的文本
+fn file_name_safe(value: &str) -> String {
+    let name: String = value
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    match name.as_str() {
+        "" => "empty".to_string(),
+        "." | ".." => name.replace('.', "_"),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellValue, ExcelRow};
+
+    fn data() -> ExcelData {
+        let mut data = ExcelData::new(
+            "sales".to_string(),
+            vec!["Region".to_string(), "Amount".to_string()],
+        );
+        for (region, amount) in [
+            ("north", 1.0),
+            ("south/east", 2.0),
+            ("north", 3.0),
+            ("", 4.0),
+        ] {
+            let mut row = HashMap::new();
+            row.insert("Region".to_string(), CellValue::String(region.to_string()));
+            row.insert("Amount".to_string(), CellValue::Number(amount));
+            data.add_row(ExcelRow { data: row });
+        }
+        data
+    }
+
+    #[test]
+    fn test_split_by_column_and_rows() {
+        let splitter = DataSplitter::new().with_column("Region".to_string());
+        let chunks = splitter.split(&data()).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].data.row_count(), 2);
+        let paths = splitter.output_paths("out/{Region}.csv", &chunks).unwrap();
+        assert_eq!(
+            paths,
+            vec!["out/north.csv", "out/south_east.csv", "out/empty.csv"]
+        );
+        assert!(splitter.output_paths("out/all.csv", &chunks).is_err());
+        assert!(splitter.output_paths("out/{City}.csv", &chunks).is_err());
+
+        let splitter = DataSplitter::new().with_max_rows(3);
+        let chunks = splitter.split(&data()).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].data.row_count(), 1);
+        let paths = splitter.output_paths("out/part-{n}.sql", &chunks).unwrap();
+        assert_eq!(paths, vec!["out/part-1.sql", "out/part-2.sql"]);
+
+        let splitter = DataSplitter::new()
+            .with_column("Region".to_string())
+            .with_max_rows(1);
+        let chunks = splitter.split(&data()).unwrap();
+        assert_eq!(chunks.len(), 4);
+        let paths = splitter.output_paths("{Region}-{n}.json", &chunks).unwrap();
+        assert_eq!(paths[1], "north-2.json");
+    }
+}